@@ -7,11 +7,14 @@ pub use models::{
 };
 use models::{NodeDetails, NodeList};
 pub use options::Options;
+#[cfg(feature = "parallel")]
+pub use parallel::{a_star_search_parallel, a_star_search_parallel_in};
 
-mod helpers;
 mod implementations;
 mod models;
 mod options;
+#[cfg(feature = "parallel")]
+mod parallel;
 
 pub fn a_star_search<
     TNode: CustomNode,
@@ -30,12 +33,16 @@ pub fn a_star_search<
     let options = options.unwrap_or(&default_options);
     let mut node_list = NodeList::new(start);
     let mut timeout = Timeout::start(options.log_interval);
+    let time_limit = options.time_limit.map(Timeout::start);
 
     if !options.suppress_logs {
         debug!("[a*] starting a* search with options {:?}", options);
     }
 
     for i in 1usize..options.iteration_limit.unwrap_or(usize::MAX) {
+        if time_limit.as_ref().is_some_and(Timeout::is_done) {
+            return Err(Error::TimeLimitExceeded);
+        }
         let (parent, remaining_list_len) = node_list.get_next()?;
         if !options.suppress_logs {
             trace!(
@@ -114,6 +121,7 @@ pub fn a_star_search_all_with_max_score<
     let options = options.unwrap_or(&default_options);
     let mut node_list = NodeList::new(start);
     let mut timeout = Timeout::start(options.log_interval);
+    let time_limit = options.time_limit.map(Timeout::start);
 
     if !options.suppress_logs {
         debug!(
@@ -125,6 +133,9 @@ pub fn a_star_search_all_with_max_score<
     let mut scoring_results: Vec<NodeDetails<TNode, TNumber>> = vec![];
 
     for i in 1usize..options.iteration_limit.unwrap_or(usize::MAX) {
+        if time_limit.as_ref().is_some_and(Timeout::is_done) {
+            return Err(Error::TimeLimitExceeded);
+        }
         let parent = if let Ok((parent, remaining_list_size)) = node_list.get_next() {
             if !options.suppress_logs {
                 trace!(
@@ -246,6 +257,8 @@ fn make_results_multiple<TNode: CustomNode + Clone, TNumber: Numeric>(
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use super::*;
 
     #[derive(Clone, Hash, Eq, PartialEq, Ord, PartialOrd, Debug)]
@@ -333,6 +346,23 @@ mod tests {
         assert_eq!(solution.shortest_path_cost, 7);
     }
 
+    #[test]
+    fn should_stop_at_time_limit() {
+        let options = Options::default()
+            .with_no_logs()
+            .with_time_limit(Duration::ZERO);
+
+        let result = a_star_search(
+            TestNode(1),
+            get_successors,
+            |_| 0,
+            |_| false,
+            Some(&options),
+        );
+
+        assert!(matches!(result, Err(Error::TimeLimitExceeded)));
+    }
+
     #[test]
     fn should_find_both_paths() {
         let start = TestNode(0);
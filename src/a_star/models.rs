@@ -1,4 +1,4 @@
-use crate::a_star::helpers::GetHash;
+use crate::hashing::GetHash;
 use crate::common::{Numeric, NumericWithUnitValue};
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Debug;
@@ -19,6 +19,8 @@ impl<T: Node> CustomNode for T {
     }
 }
 
+/// Node ids and position hashes only need to be unique within one search. For ids that are
+/// stored between runs, build them with `hashing::GetHash::get_stable_hash` and `hash_combine`.
 pub trait CustomNode: Send + Sync + Debug {
     const NODE_ID_AND_POSITION_HASH_SAME: bool;
     fn get_node_id(&self) -> u64;
@@ -42,7 +44,9 @@ pub struct CurrentNodeDetails<'a, TNode: CustomNode, TNumber: Numeric> {
     pub cost_to_move_to_current: TNumber,
 }
 
+/// More variants may be added, e.g. for new kinds of limits.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     #[error("No solution found")]
     NoSolutionFound,
@@ -50,6 +54,8 @@ pub enum Error {
     UnexpectedError,
     #[error("Iteration limit exceeded")]
     IterLimitExceeded,
+    #[error("Time limit exceeded")]
+    TimeLimitExceeded,
 }
 
 pub(crate) struct NodeList<TNode: CustomNode, TNumber: Numeric> {
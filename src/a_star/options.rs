@@ -5,13 +5,14 @@ pub struct Options {
     pub(crate) log_interval: Duration,
     pub(crate) suppress_logs: bool,
     pub(crate) iteration_limit: Option<usize>,
+    pub(crate) time_limit: Option<Duration>,
 }
 
 impl Debug for Options {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "log={} interval={:?} iter_limit={:?}",
+            "log={} interval={:?} iter_limit={:?} time_limit={:?}",
             !self.suppress_logs,
             if self.suppress_logs {
                 None
@@ -19,6 +20,7 @@ impl Debug for Options {
                 Some(self.log_interval)
             },
             self.iteration_limit,
+            self.time_limit,
         )
     }
 }
@@ -36,6 +38,11 @@ impl Options {
         self.iteration_limit = Some(limit);
         self
     }
+    /// Gives up with `Error::TimeLimitExceeded` once the search has run for `limit`.
+    pub fn with_time_limit(mut self, limit: Duration) -> Self {
+        self.time_limit = Some(limit);
+        self
+    }
 }
 
 impl Default for Options {
@@ -44,6 +51,7 @@ impl Default for Options {
             log_interval: Duration::from_secs(5),
             suppress_logs: false,
             iteration_limit: None,
+            time_limit: None,
         }
     }
 }
@@ -0,0 +1,451 @@
+use std::cmp::Ordering as CmpOrdering;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use log::*;
+
+use crate::a_star::models::{
+    ComputationResult, CurrentNodeDetails, CustomNode, Error, Result, Successor,
+};
+use crate::a_star::options::Options;
+use crate::common::Numeric;
+use crate::num_cpus::ThreadPool;
+use crate::parallel::default_pool;
+use crate::timeout::Timeout;
+
+struct Candidate<TNode, TNumber> {
+    node: TNode,
+    accrued_cost: TNumber,
+    estimated_cost: TNumber,
+    parent: Option<u64>,
+}
+
+impl<TNode, TNumber: Numeric> Candidate<TNode, TNumber> {
+    fn total_cost(&self) -> TNumber {
+        self.accrued_cost + self.estimated_cost
+    }
+}
+
+// reversed so `BinaryHeap` pops the cheapest candidate
+impl<TNode, TNumber: Numeric> Ord for Candidate<TNode, TNumber> {
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        other.total_cost().cmp(&self.total_cost())
+    }
+}
+
+impl<TNode, TNumber: Numeric> PartialOrd for Candidate<TNode, TNumber> {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<TNode, TNumber: Numeric> PartialEq for Candidate<TNode, TNumber> {
+    fn eq(&self, other: &Self) -> bool {
+        self.total_cost() == other.total_cost()
+    }
+}
+
+impl<TNode, TNumber: Numeric> Eq for Candidate<TNode, TNumber> {}
+
+struct Goal<TNode, TNumber> {
+    node: TNode,
+    cost: TNumber,
+    parent: u64,
+}
+
+/// Expanded nodes, keyed by node id, for walking back from the goal.
+type History<TNode> = HashMap<u64, (TNode, Option<u64>)>;
+
+type Inbox<TNode, TNumber> = Receiver<Candidate<TNode, TNumber>>;
+
+/// How long a worker waits with nothing to do before it adopts an inbox nobody has claimed.
+const ADOPT_AFTER: Duration = Duration::from_millis(10);
+
+struct Shared<'a, TNode, TNumber, S, D, E> {
+    inboxes: &'a [Sender<Candidate<TNode, TNumber>>],
+    /// Candidates in an inbox or an open set. Zero means the search is exhausted.
+    pending: AtomicUsize,
+    expansions: AtomicUsize,
+    /// Set when either limit stops the search.
+    limit_exceeded: AtomicBool,
+    time_limit: Option<Timeout>,
+    timed_out: AtomicBool,
+    goal: Mutex<Option<Goal<TNode, TNumber>>>,
+    get_successors: S,
+    distance_function: D,
+    is_at_end_function: E,
+    options: &'a Options,
+}
+
+impl<TNode, TNumber, S, D, E> Shared<'_, TNode, TNumber, S, D, E>
+where
+    TNode: CustomNode,
+    TNumber: Numeric + Send,
+    S: Fn(&TNode) -> Vec<Successor<TNode, TNumber>> + Sync,
+    D: Fn(CurrentNodeDetails<TNode, TNumber>) -> TNumber + Sync,
+    E: Fn(&TNode) -> bool + Sync,
+{
+    fn send(&self, candidate: Candidate<TNode, TNumber>) {
+        let owner = candidate.node.get_position_hash() as usize % self.inboxes.len();
+        self.pending.fetch_add(1, Ordering::SeqCst);
+        self.inboxes[owner]
+            .send(candidate)
+            .expect("a* workers outlive the search");
+    }
+    fn goal_cost(&self) -> Option<TNumber> {
+        self.goal.lock().unwrap().as_ref().map(|goal| goal.cost)
+    }
+    /// Claims an unclaimed inbox and searches the nodes sent to it. A worker that has been idle
+    /// for `ADOPT_AFTER` also adopts inboxes whose job hasn't started, e.g. because the pool is
+    /// busy with other work, so the search never waits on a job that can't run.
+    fn work(&self, unclaimed: &Mutex<Vec<Inbox<TNode, TNumber>>>) -> History<TNode> {
+        let claim = || unclaimed.lock().unwrap().pop();
+        let Some(inbox) = claim() else {
+            return History::new();
+        };
+        let mut inboxes = vec![inbox];
+        let mut idle_since = None;
+        let mut open = BinaryHeap::new();
+        let mut best_costs: HashMap<u64, TNumber> = HashMap::new();
+        let mut history = History::new();
+        let compare = if TNode::NODE_ID_AND_POSITION_HASH_SAME {
+            TNumber::le
+        } else {
+            TNumber::lt
+        };
+        let mut receive =
+            |candidate: Candidate<TNode, TNumber>,
+             open: &mut BinaryHeap<Candidate<TNode, TNumber>>| {
+                let position = candidate.node.get_position_hash();
+                match best_costs.get(&position) {
+                    Some(existing) if compare(existing, &candidate.accrued_cost) => {
+                        self.pending.fetch_sub(1, Ordering::SeqCst);
+                    }
+                    _ => {
+                        best_costs.insert(position, candidate.accrued_cost);
+                        open.push(candidate);
+                    }
+                }
+            };
+        while !self.limit_exceeded.load(Ordering::Relaxed) {
+            for inbox in &inboxes {
+                while let Ok(candidate) = inbox.try_recv() {
+                    receive(candidate, &mut open);
+                }
+            }
+            let Some(candidate) = open.pop() else {
+                if self.pending.load(Ordering::SeqCst) == 0 {
+                    break;
+                }
+                let idle_since = idle_since.get_or_insert_with(Instant::now);
+                if idle_since.elapsed() >= ADOPT_AFTER {
+                    if let Some(inbox) = claim() {
+                        inboxes.push(inbox);
+                        continue;
+                    }
+                }
+                if let Ok(candidate) = inboxes[0].recv_timeout(Duration::from_millis(1)) {
+                    receive(candidate, &mut open);
+                }
+                continue;
+            };
+            idle_since = None;
+            if self
+                .goal_cost()
+                .is_none_or(|goal| candidate.total_cost() < goal)
+            {
+                self.expand(candidate, &mut history);
+            }
+            self.pending.fetch_sub(1, Ordering::SeqCst);
+        }
+        history
+    }
+    fn expand(&self, candidate: Candidate<TNode, TNumber>, history: &mut History<TNode>) {
+        let expansions = self.expansions.fetch_add(1, Ordering::Relaxed) + 1;
+        if expansions >= self.options.iteration_limit.unwrap_or(usize::MAX) {
+            self.limit_exceeded.store(true, Ordering::Relaxed);
+            return;
+        }
+        if self.time_limit.as_ref().is_some_and(Timeout::is_done) {
+            self.timed_out.store(true, Ordering::Relaxed);
+            self.limit_exceeded.store(true, Ordering::Relaxed);
+            return;
+        }
+        if !self.options.suppress_logs {
+            trace!(
+                "[a*] step={} got {:?} with accrued={}",
+                expansions,
+                candidate.node,
+                candidate.accrued_cost
+            );
+        }
+        let id = candidate.node.get_node_id();
+        for Successor {
+            node: successor,
+            cost_to_move_here: distance,
+        } in (self.get_successors)(&candidate.node)
+        {
+            let to_current = candidate.accrued_cost + distance;
+            if (self.is_at_end_function)(&successor) {
+                let mut goal = self.goal.lock().unwrap();
+                if goal.as_ref().is_none_or(|goal| to_current < goal.cost) {
+                    *goal = Some(Goal {
+                        node: successor,
+                        cost: to_current,
+                        parent: id,
+                    });
+                }
+                continue;
+            }
+            let to_end = (self.distance_function)(CurrentNodeDetails {
+                current_node: &successor,
+                cost_to_move_to_current: to_current,
+            });
+            if self
+                .goal_cost()
+                .is_some_and(|goal| goal <= to_current + to_end)
+            {
+                continue;
+            }
+            self.send(Candidate {
+                node: successor,
+                accrued_cost: to_current,
+                estimated_cost: to_end,
+                parent: Some(id),
+            });
+        }
+        history.insert(id, (candidate.node, candidate.parent));
+    }
+}
+
+/// Experimental HDA*: each worker owns the open set for the nodes whose position hash maps to
+/// it, and successors are sent to their owner. This is plain HDA*, without work stealing between
+/// open sets. Runs on a shared pool with one worker per CPU, so the functions must be
+/// `Fn + Sync`. Returns the cheapest path to an end node that was found.
+pub fn a_star_search_parallel<
+    TNode: CustomNode,
+    TSuccessorsFunc: Fn(&TNode) -> Vec<Successor<TNode, TNumber>> + Sync,
+    TDistanceFunc: Fn(CurrentNodeDetails<TNode, TNumber>) -> TNumber + Sync,
+    TEndCheckFunc: Fn(&TNode) -> bool + Sync,
+    TNumber: Numeric + Send,
+>(
+    start: TNode,
+    get_successors: TSuccessorsFunc,
+    distance_function: TDistanceFunc,
+    is_at_end_function: TEndCheckFunc,
+    options: Option<&Options>,
+) -> Result<ComputationResult<TNode, TNumber>> {
+    a_star_search_parallel_in(
+        default_pool(),
+        start,
+        get_successors,
+        distance_function,
+        is_at_end_function,
+        options,
+    )
+}
+
+/// Like `a_star_search_parallel`, with one open set per worker of `pool`.
+pub fn a_star_search_parallel_in<
+    TNode: CustomNode,
+    TSuccessorsFunc: Fn(&TNode) -> Vec<Successor<TNode, TNumber>> + Sync,
+    TDistanceFunc: Fn(CurrentNodeDetails<TNode, TNumber>) -> TNumber + Sync,
+    TEndCheckFunc: Fn(&TNode) -> bool + Sync,
+    TNumber: Numeric + Send,
+>(
+    pool: &ThreadPool,
+    start: TNode,
+    get_successors: TSuccessorsFunc,
+    distance_function: TDistanceFunc,
+    is_at_end_function: TEndCheckFunc,
+    options: Option<&Options>,
+) -> Result<ComputationResult<TNode, TNumber>> {
+    let default_options = Options::default();
+    let options = options.unwrap_or(&default_options);
+    if !options.suppress_logs {
+        debug!(
+            "[a*] starting parallel a* search on {} workers with options {:?}",
+            pool.size(),
+            options
+        );
+    }
+
+    let (inboxes, receivers): (Vec<_>, Vec<_>) = (0..pool.size()).map(|_| channel()).unzip();
+    let shared = Shared {
+        inboxes: &inboxes,
+        pending: AtomicUsize::new(0),
+        expansions: AtomicUsize::new(0),
+        limit_exceeded: AtomicBool::new(false),
+        time_limit: options.time_limit.map(Timeout::start),
+        timed_out: AtomicBool::new(false),
+        goal: Mutex::new(None),
+        get_successors,
+        distance_function,
+        is_at_end_function,
+        options,
+    };
+    shared.send(Candidate {
+        node: start,
+        accrued_cost: TNumber::default(),
+        estimated_cost: TNumber::default(),
+        parent: None,
+    });
+    let workers = receivers.len();
+    let unclaimed = Mutex::new(receivers);
+    let histories = Mutex::new(Vec::with_capacity(workers));
+    pool.scope(|scope| {
+        for _ in 0..workers {
+            let (shared, unclaimed, histories) = (&shared, &unclaimed, &histories);
+            scope.spawn(move || {
+                let history = shared.work(unclaimed);
+                histories.lock().unwrap().push(history);
+            })
+        }
+    });
+
+    if shared.timed_out.into_inner() {
+        return Err(Error::TimeLimitExceeded);
+    }
+    if shared.limit_exceeded.into_inner() {
+        return Err(Error::IterLimitExceeded);
+    }
+    let goal = shared
+        .goal
+        .into_inner()
+        .unwrap()
+        .ok_or(Error::NoSolutionFound)?;
+    if !options.suppress_logs {
+        debug!("[a*] took {} steps", shared.expansions.into_inner());
+    }
+    let mut history: History<TNode> = histories
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect();
+    let mut shortest_path = vec![goal.node];
+    let mut parent = Some(goal.parent);
+    while let Some(id) = parent {
+        let (node, next) = history.remove(&id).ok_or(Error::UnexpectedError)?;
+        shortest_path.push(node);
+        parent = next;
+    }
+    shortest_path.reverse();
+    Ok(ComputationResult {
+        shortest_path,
+        shortest_path_cost: goal.cost,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::a_star::{a_star_search, Node};
+
+    #[derive(Clone, Copy, Hash, Eq, PartialEq, Debug)]
+    struct Cell(i32, i32);
+
+    impl Node for Cell {}
+
+    const SIZE: i32 = 30;
+
+    fn is_wall(Cell(x, y): Cell) -> bool {
+        x == SIZE / 2 && y < SIZE - 3
+    }
+
+    fn get_successors(cell: &Cell) -> Vec<Successor<Cell, i32>> {
+        [(0, 1), (1, 0), (0, -1), (-1, 0)]
+            .into_iter()
+            .map(|(dx, dy)| Cell(cell.0 + dx, cell.1 + dy))
+            .filter(|&Cell(x, y)| (0..SIZE).contains(&x) && (0..SIZE).contains(&y))
+            .filter(|&cell| !is_wall(cell))
+            .map(|cell| Successor::new(cell, 1))
+            .collect()
+    }
+
+    #[test]
+    fn should_match_sequential_search() {
+        let target = Cell(SIZE - 1, 0);
+        let distance = |details: CurrentNodeDetails<Cell, i32>| {
+            (details.current_node.0 - target.0).abs() + (details.current_node.1 - target.1).abs()
+        };
+        let options = Options::default().with_no_logs();
+        let expected = a_star_search(
+            Cell(0, 0),
+            get_successors,
+            distance,
+            |cell| cell == &target,
+            Some(&options),
+        )
+        .unwrap();
+        let solution = a_star_search_parallel(
+            Cell(0, 0),
+            get_successors,
+            distance,
+            |cell| cell == &target,
+            Some(&options),
+        )
+        .unwrap();
+        assert_eq!(solution.shortest_path_cost, expected.shortest_path_cost);
+        assert_eq!(solution.shortest_path.first(), Some(&Cell(0, 0)));
+        assert_eq!(solution.shortest_path.last(), Some(&target));
+        assert_eq!(
+            solution.shortest_path.len() as i32,
+            solution.shortest_path_cost + 1
+        );
+    }
+
+    #[test]
+    fn should_finish_on_a_busy_pool() {
+        // one worker is blocked until the goal is found, so the other has to adopt its inbox
+        let pool = ThreadPool::new(2);
+        let (found, blocked) = channel::<()>();
+        pool.execute(move || {
+            blocked.recv().ok();
+        });
+        let target = Cell(SIZE - 1, 0);
+        let options = Options::default().with_no_logs();
+        let solution = a_star_search_parallel_in(
+            &pool,
+            Cell(0, 0),
+            get_successors,
+            |_| 0,
+            |cell| {
+                let at_end = cell == &target;
+                if at_end {
+                    found.send(()).ok();
+                }
+                at_end
+            },
+            Some(&options),
+        )
+        .unwrap();
+        assert_eq!(solution.shortest_path.last(), Some(&target));
+    }
+
+    #[test]
+    fn should_report_missing_solution() {
+        let options = Options::default().with_no_logs();
+        let result = a_star_search_parallel(
+            Cell(0, 0),
+            get_successors,
+            |_| 0,
+            |cell| cell == &Cell(SIZE, SIZE),
+            Some(&options),
+        );
+        assert!(matches!(result, Err(Error::NoSolutionFound)));
+
+        let options = options.with_iteration_limit(10);
+        let result = a_star_search_parallel(
+            Cell(0, 0),
+            get_successors,
+            |_| 0,
+            |cell| cell == &Cell(SIZE, SIZE),
+            Some(&options),
+        );
+        assert!(matches!(result, Err(Error::IterLimitExceeded)));
+    }
+}
@@ -0,0 +1,117 @@
+use std::num::ParseIntError;
+
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Can't parse {text:?} as base {radix}: {source}")]
+pub struct ParseBitsError {
+    pub text: String,
+    pub radix: u32,
+    #[source]
+    pub source: ParseIntError,
+}
+
+/// Parses `101`, `0b101` or `1010_0101`.
+pub fn parse_binary(text: &str) -> Result<u64, ParseBitsError> {
+    parse_radix(text, "0b", 2)
+}
+
+/// Parses `ff`, `0xFF` or `dead_beef`.
+pub fn parse_hex(text: &str) -> Result<u64, ParseBitsError> {
+    parse_radix(text, "0x", 16)
+}
+
+fn parse_radix(text: &str, prefix: &str, radix: u32) -> Result<u64, ParseBitsError> {
+    let trimmed = text.trim();
+    let digits = trimmed
+        .strip_prefix(prefix)
+        .or_else(|| trimmed.strip_prefix(&prefix.to_uppercase()))
+        .unwrap_or(trimmed)
+        .replace('_', "");
+    u64::from_str_radix(&digits, radix).map_err(|source| ParseBitsError {
+        text: text.to_string(),
+        radix,
+        source,
+    })
+}
+
+/// Positions of the set bits, lowest first.
+pub fn set_bits(value: u64) -> impl Iterator<Item = u32> {
+    let mut bits = value;
+    std::iter::from_fn(move || {
+        if bits == 0 {
+            return None;
+        }
+        let position = bits.trailing_zeros();
+        bits &= bits - 1;
+        Some(position)
+    })
+}
+
+fn mask(width: u32) -> u64 {
+    if width >= u64::BITS {
+        u64::MAX
+    } else {
+        (1 << width) - 1
+    }
+}
+
+/// The `width` bits starting at bit `offset`. Bits past the 64th read as 0.
+pub fn get_field(value: u64, offset: u32, width: u32) -> u64 {
+    value.checked_shr(offset).unwrap_or(0) & mask(width)
+}
+
+/// `value` with the `width` bits at `offset` replaced by the low bits of `field`. Bits past the
+/// 64th are dropped, so an `offset` of 64 or more leaves `value` unchanged.
+pub fn set_field(value: u64, offset: u32, width: u32, field: u64) -> u64 {
+    let mask = mask(width).checked_shl(offset).unwrap_or(0);
+    (value & !mask) | (field.checked_shl(offset).unwrap_or(0) & mask)
+}
+
+/// Consecutive numbers differ in exactly one bit of their Gray codes.
+pub fn to_gray(value: u64) -> u64 {
+    value ^ (value >> 1)
+}
+
+pub fn from_gray(gray: u64) -> u64 {
+    let mut value = gray;
+    let mut shift = 1;
+    while shift < u64::BITS {
+        value ^= value >> shift;
+        shift *= 2;
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_bits() {
+        assert_eq!(parse_binary("0b1010_0101"), Ok(0xa5));
+        assert_eq!(parse_hex(" 0xFF "), Ok(255));
+        assert_eq!(parse_hex("dead_beef"), Ok(0xdead_beef));
+        assert_eq!(
+            parse_binary("102").unwrap_err().to_string(),
+            r#"Can't parse "102" as base 2: invalid digit found in string"#
+        );
+    }
+
+    #[test]
+    fn should_manipulate_bits() {
+        assert_eq!(set_bits(0b1001_0010).collect::<Vec<_>>(), [1, 4, 7]);
+        assert_eq!(get_field(0xabcd, 4, 8), 0xbc);
+        assert_eq!(set_field(0xabcd, 4, 8, 0x1ff), 0xaffd);
+        assert_eq!(get_field(u64::MAX, 0, 64), u64::MAX);
+        assert_eq!(get_field(u64::MAX, 63, 8), 1);
+        assert_eq!(get_field(u64::MAX, 64, 8), 0);
+        assert_eq!(set_field(0, 60, 8, 0xff), 0xf << 60);
+        assert_eq!(set_field(0xabcd, 64, 8, 0xff), 0xabcd);
+        assert_eq!(set_field(0xabcd, u32::MAX, 64, 0xff), 0xabcd);
+        for n in 0..100 {
+            assert_eq!((to_gray(n) ^ to_gray(n + 1)).count_ones(), 1);
+            assert_eq!(from_gray(to_gray(n)), n);
+        }
+    }
+}
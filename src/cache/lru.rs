@@ -0,0 +1,135 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// Holds at most `capacity` entries, evicting the least recently used one to make room.
+pub struct LruCache<K, V> {
+    capacity: usize,
+    entries: HashMap<K, (V, u64)>,
+    /// Keys by the tick they were last used on, oldest first.
+    recency: BTreeMap<u64, K>,
+    tick: u64,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    /// Most entries allocated for up front; a huge `capacity` is only a limit.
+    const MAX_PREALLOCATED: usize = 1024;
+
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::with_capacity(capacity.min(Self::MAX_PREALLOCATED)),
+            recency: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+    /// Marks the entry as most recently used.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.touch(key)?;
+        self.entries.get(key).map(|(value, _)| value)
+    }
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.touch(key)?;
+        self.entries.get_mut(key).map(|(value, _)| value)
+    }
+    /// Like `get`, without changing the recency order.
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.entries.get(key).map(|(value, _)| value)
+    }
+    /// Returns the value previously stored under `key`, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let previous = self.remove(&key);
+        if self.entries.len() >= self.capacity {
+            self.pop_least_recent();
+        }
+        self.tick += 1;
+        self.recency.insert(self.tick, key.clone());
+        self.entries.insert(key, (value, self.tick));
+        previous
+    }
+    pub fn get_or_insert_with(&mut self, key: K, f: impl FnOnce() -> V) -> &V {
+        if self.touch(&key).is_none() {
+            self.insert(key.clone(), f());
+        }
+        &self.entries[&key].0
+    }
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (value, tick) = self.entries.remove(key)?;
+        self.recency.remove(&tick);
+        Some(value)
+    }
+    pub fn pop_least_recent(&mut self) -> Option<(K, V)> {
+        let (_, key) = self.recency.pop_first()?;
+        let (value, _) = self.entries.remove(&key)?;
+        Some((key, value))
+    }
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+    /// Most recently used first.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.recency
+            .values()
+            .rev()
+            .map(|key| (key, &self.entries[key].0))
+    }
+    fn touch(&mut self, key: &K) -> Option<()> {
+        let (_, tick) = self.entries.get_mut(key)?;
+        let key = self.recency.remove(tick)?;
+        self.tick += 1;
+        *tick = self.tick;
+        self.recency.insert(self.tick, key);
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_evict_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        assert_eq!(cache.get(&"a"), Some(&1));
+        cache.insert("c", 3);
+        assert!(!cache.contains_key(&"b"));
+        assert_eq!(cache.iter().collect::<Vec<_>>(), [(&"c", &3), (&"a", &1)]);
+
+        assert_eq!(cache.insert("a", 10), Some(1));
+        assert_eq!(cache.peek(&"c"), Some(&3));
+        assert_eq!(cache.pop_least_recent(), Some(("c", 3)));
+        assert_eq!(cache.len(), 1);
+
+        let mut unbounded = LruCache::new(usize::MAX);
+        unbounded.insert(1, 1);
+        assert_eq!(unbounded.capacity(), usize::MAX);
+    }
+
+    #[test]
+    fn should_get_or_insert() {
+        let mut cache = LruCache::new(3);
+        let mut calls = 0;
+        for key in [1, 2, 1, 3, 4, 1] {
+            cache.get_or_insert_with(key, || {
+                calls += 1;
+                key * 10
+            });
+        }
+        assert_eq!(calls, 4);
+        assert_eq!(cache.iter().map(|(k, _)| *k).collect::<Vec<_>>(), [1, 4, 3]);
+    }
+}
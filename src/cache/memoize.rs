@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Wraps `f` so each distinct argument is computed once. For recursive functions, see
+/// `memoize!`.
+pub fn memoize<A: Hash + Eq + Clone, R: Clone>(mut f: impl FnMut(A) -> R) -> impl FnMut(A) -> R {
+    let mut cache = HashMap::new();
+    move |arg: A| {
+        cache
+            .entry(arg)
+            .or_insert_with_key(|arg| f(arg.clone()))
+            .clone()
+    }
+}
+
+/// Defines a function whose results are cached in a `HashMap` passed as its first argument,
+/// keyed on the tuple of the other arguments. `fn fib(cache, n: u64) -> u64 { .. }` recurses
+/// with `fib(cache, n - 1)` and is called as `fib(&mut HashMap::new(), 90)`.
+#[macro_export]
+macro_rules! memoize {
+    (
+    $(#[$attr:meta])*
+    $vis:vis fn $name:ident($cache:ident $(, $arg:ident: $ty:ty)* $(,)?) -> $ret:ty $body:block
+) => {
+        $(#[$attr])*
+        $vis fn $name(
+            $cache: &mut ::std::collections::HashMap<($($ty,)*), $ret>,
+            $($arg: $ty),*
+        ) -> $ret {
+            let key = ($(::std::clone::Clone::clone(&$arg),)*);
+            if let ::std::option::Option::Some(result) = $cache.get(&key) {
+                return ::std::clone::Clone::clone(result);
+            }
+            let result: $ret = (|| $body)();
+            $cache.insert(key, ::std::clone::Clone::clone(&result));
+            result
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::memoize! {
+        fn paths(cache, width: usize, height: usize) -> u64 {
+            if width == 0 || height == 0 {
+                return 1;
+            }
+            paths(cache, width - 1, height) + paths(cache, width, height - 1)
+        }
+    }
+
+    #[test]
+    fn should_memoize() {
+        let mut calls = 0;
+        let mut square = memoize(|n: u64| {
+            calls += 1;
+            n * n
+        });
+        assert_eq!(square(3), 9);
+        assert_eq!(square(3), 9);
+        assert_eq!(square(4), 16);
+        drop(square);
+        assert_eq!(calls, 2);
+
+        let mut cache = HashMap::new();
+        assert_eq!(paths(&mut cache, 16, 16), 601080390);
+        assert_eq!(cache.len(), 17 * 17 - 1);
+    }
+}
@@ -0,0 +1,5 @@
+mod lru;
+mod memoize;
+
+pub use lru::LruCache;
+pub use memoize::memoize;
@@ -0,0 +1,197 @@
+use std::collections::VecDeque;
+use std::io::Write;
+use std::sync::Once;
+use std::time::Duration;
+
+use crossterm::event::Event;
+use crossterm::{cursor, terminal, ExecutableCommand, QueueableCommand};
+
+use crate::canvas::buffer::Buffer;
+
+/// Where a `Canvas` sends its frames. Each frame arrives both as the bytes a terminal would
+/// receive and as the cells they describe, so a backend can use whichever suits it.
+pub trait Backend: Send {
+    fn init(&mut self) -> std::io::Result<()>;
+    fn restore(&mut self) -> std::io::Result<()>;
+    fn size(&self) -> std::io::Result<(u16, u16)>;
+    fn clear(&mut self) -> std::io::Result<()>;
+    fn write_frame(&mut self, output: &[u8], cells: &Buffer) -> std::io::Result<()>;
+    fn set_raw_mode(&mut self, enabled: bool) -> std::io::Result<()>;
+    fn poll_event(&mut self, timeout: Duration) -> std::io::Result<Option<Event>>;
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Output {
+    #[default]
+    Stdout,
+    Stderr,
+}
+
+impl Output {
+    fn writer(self) -> Box<dyn Write + Send> {
+        match self {
+            Output::Stdout => Box::new(std::io::stdout()),
+            Output::Stderr => Box::new(std::io::stderr()),
+        }
+    }
+}
+
+/// Installs a panic hook that shows the cursor, leaves raw mode and the alternate screen, then
+/// hands over to the previous hook. Only the first call has any effect.
+pub fn restore_terminal_on_panic(output: Output) {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let mut out = output.writer();
+            let _ = terminal::disable_raw_mode();
+            let _ = out.queue(terminal::LeaveAlternateScreen);
+            let _ = out.queue(cursor::Show);
+            let _ = out.write_all(b"\r\n");
+            let _ = out.flush();
+            previous(info);
+        }));
+    });
+}
+
+pub struct TerminalBackend {
+    out: Box<dyn Write + Send>,
+    pub(crate) hide_cursor: bool,
+    pub(crate) clear_on_start: bool,
+    pub(crate) clear_on_drop: bool,
+}
+
+impl TerminalBackend {
+    pub fn new() -> Self {
+        Self::with_output(Output::Stdout)
+    }
+    pub fn with_output(output: Output) -> Self {
+        Self {
+            out: output.writer(),
+            hide_cursor: true,
+            clear_on_start: true,
+            clear_on_drop: false,
+        }
+    }
+}
+
+impl Default for TerminalBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Backend for TerminalBackend {
+    fn init(&mut self) -> std::io::Result<()> {
+        if self.hide_cursor {
+            self.out.execute(cursor::Hide)?;
+        }
+        if self.clear_on_start {
+            self.out.queue(terminal::Clear(terminal::ClearType::All))?;
+            self.out.queue(cursor::MoveToRow(0))?;
+        }
+        Ok(())
+    }
+    fn restore(&mut self) -> std::io::Result<()> {
+        if self.clear_on_drop {
+            // frames leave the cursor where the canvas starts
+            self.out
+                .queue(terminal::Clear(terminal::ClearType::FromCursorDown))?;
+        }
+        if self.hide_cursor {
+            self.out.queue(cursor::Show)?;
+        }
+        self.out.flush()
+    }
+    fn size(&self) -> std::io::Result<(u16, u16)> {
+        terminal::size()
+    }
+    fn clear(&mut self) -> std::io::Result<()> {
+        self.out.queue(terminal::Clear(terminal::ClearType::All))?;
+        self.out.queue(cursor::MoveTo(0, 0))?;
+        Ok(())
+    }
+    fn write_frame(&mut self, output: &[u8], _cells: &Buffer) -> std::io::Result<()> {
+        self.out.write_all(output)?;
+        self.out.flush()
+    }
+    fn set_raw_mode(&mut self, enabled: bool) -> std::io::Result<()> {
+        if enabled {
+            terminal::enable_raw_mode()
+        } else {
+            terminal::disable_raw_mode()
+        }
+    }
+    fn poll_event(&mut self, timeout: Duration) -> std::io::Result<Option<Event>> {
+        if crossterm::event::poll(timeout)? {
+            crossterm::event::read().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Keeps frames in memory instead of drawing them, so rendering code can be unit-tested.
+/// Events queued with `push_event` are handed out by `Canvas::poll_event`.
+#[derive(Clone, Debug, Default)]
+pub struct HeadlessBackend {
+    size: (u16, u16),
+    frames: Vec<Buffer>,
+    events: VecDeque<Event>,
+    raw_mode: bool,
+}
+
+impl HeadlessBackend {
+    pub fn new(size: (u16, u16)) -> Self {
+        Self {
+            size,
+            ..Default::default()
+        }
+    }
+    pub fn frames(&self) -> &[Buffer] {
+        &self.frames
+    }
+    pub fn last_frame(&self) -> Option<&Buffer> {
+        self.frames.last()
+    }
+    /// The last frame's characters, see `Buffer::to_text`.
+    pub fn last_text(&self) -> String {
+        self.last_frame().map(Buffer::to_text).unwrap_or_default()
+    }
+    pub fn push_event(&mut self, event: Event) {
+        self.events.push_back(event);
+    }
+    /// Changes the reported size; the canvas picks it up on its next frame.
+    pub fn resize(&mut self, size: (u16, u16)) {
+        self.size = size;
+    }
+    pub fn is_raw_mode(&self) -> bool {
+        self.raw_mode
+    }
+}
+
+impl Backend for HeadlessBackend {
+    fn init(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+    fn restore(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+    fn size(&self) -> std::io::Result<(u16, u16)> {
+        Ok(self.size)
+    }
+    fn clear(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+    fn write_frame(&mut self, _output: &[u8], cells: &Buffer) -> std::io::Result<()> {
+        self.frames.push(cells.clone());
+        Ok(())
+    }
+    fn set_raw_mode(&mut self, enabled: bool) -> std::io::Result<()> {
+        self.raw_mode = enabled;
+        Ok(())
+    }
+    fn poll_event(&mut self, _timeout: Duration) -> std::io::Result<Option<Event>> {
+        Ok(self.events.pop_front())
+    }
+}
@@ -0,0 +1,163 @@
+use std::fmt::{Display, Formatter};
+
+use crate::canvas::buffer::Buffer;
+use crate::canvas::shapes::line_points;
+use crate::canvas::style::{Cell, Style};
+
+/// A pixel surface where every terminal cell holds a 2×4 block of dots, drawn with the Unicode
+/// braille patterns (U+2800..U+28FF).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BrailleCanvas {
+    columns: usize,
+    rows: usize,
+    cells: Vec<u8>,
+}
+
+impl BrailleCanvas {
+    const DOTS: [[u8; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+    /// A canvas covering `columns`×`rows` terminal cells, i.e. `2 * columns` by `4 * rows` pixels.
+    pub fn new(columns: usize, rows: usize) -> Self {
+        Self {
+            columns,
+            rows,
+            cells: vec![0; columns * rows],
+        }
+    }
+    pub fn width(&self) -> usize {
+        self.columns * 2
+    }
+    pub fn height(&self) -> usize {
+        self.rows * 4
+    }
+    /// Pixels outside the canvas are ignored.
+    pub fn set_pixel(&mut self, x: i64, y: i64) {
+        if let Some((index, bit)) = self.locate(x, y) {
+            self.cells[index] |= bit;
+        }
+    }
+    pub fn unset_pixel(&mut self, x: i64, y: i64) {
+        if let Some((index, bit)) = self.locate(x, y) {
+            self.cells[index] &= !bit;
+        }
+    }
+    pub fn get_pixel(&self, x: i64, y: i64) -> bool {
+        self.locate(x, y)
+            .is_some_and(|(index, bit)| self.cells[index] & bit != 0)
+    }
+    pub fn line(&mut self, from: (i64, i64), to: (i64, i64)) {
+        for (x, y) in line_points(from, to) {
+            self.set_pixel(x, y);
+        }
+    }
+    /// Plots `f` over `x_range`, scaling `y_range` to the canvas height (larger y is up).
+    pub fn plot(&mut self, f: impl Fn(f64) -> f64, x_range: (f64, f64), y_range: (f64, f64)) {
+        let (width, height) = (self.width(), self.height());
+        if width == 0 || height == 0 {
+            return;
+        }
+        let to_row = |y: f64| {
+            let fraction = (y - y_range.0) / (y_range.1 - y_range.0);
+            ((1.0 - fraction) * (height - 1) as f64).round() as i64
+        };
+        let mut previous: Option<(i64, i64)> = None;
+        for column in 0..width {
+            let x = x_range.0 + (x_range.1 - x_range.0) * column as f64 / (width - 1).max(1) as f64;
+            let y = f(x);
+            if !y.is_finite() {
+                previous = None;
+                continue;
+            }
+            let point = (column as i64, to_row(y));
+            match previous {
+                Some(previous) => self.line(previous, point),
+                None => self.set_pixel(point.0, point.1),
+            }
+            previous = Some(point);
+        }
+    }
+    pub fn clear(&mut self) {
+        self.cells.fill(0);
+    }
+    pub fn rows(&self) -> Vec<String> {
+        self.cells
+            .chunks(self.columns.max(1))
+            .take(self.rows)
+            .map(|row| row.iter().map(|&bits| Self::to_char(bits)).collect())
+            .collect()
+    }
+    /// Copies the dots into `buffer` at `(x, y)`. Empty cells are left untouched.
+    pub fn draw_onto(&self, buffer: &mut Buffer, x: u16, y: u16, style: Style) {
+        for (row, chunk) in self.cells.chunks(self.columns.max(1)).enumerate() {
+            for (column, &bits) in chunk.iter().enumerate() {
+                if bits != 0 {
+                    buffer.set_cell(
+                        x.saturating_add(column as u16),
+                        y.saturating_add(row as u16),
+                        Cell::new(Self::to_char(bits), style),
+                    );
+                }
+            }
+        }
+    }
+    fn to_char(bits: u8) -> char {
+        char::from_u32(0x2800 + bits as u32).unwrap()
+    }
+    fn locate(&self, x: i64, y: i64) -> Option<(usize, u8)> {
+        if x < 0 || y < 0 || x as usize >= self.width() || y as usize >= self.height() {
+            return None;
+        }
+        let (x, y) = (x as usize, y as usize);
+        Some(((y / 4) * self.columns + x / 2, Self::DOTS[x % 2][y % 4]))
+    }
+}
+
+impl Display for BrailleCanvas {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.rows().join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_map_pixels_to_braille_dots() {
+        let mut canvas = BrailleCanvas::new(2, 1);
+        assert_eq!(canvas.to_string(), "⠀⠀");
+        canvas.set_pixel(0, 0);
+        canvas.set_pixel(1, 3);
+        canvas.set_pixel(3, 1);
+        canvas.set_pixel(4, 0);
+        canvas.set_pixel(-1, 0);
+        assert_eq!(canvas.to_string(), "⢁⠐");
+        assert!(canvas.get_pixel(1, 3));
+        canvas.unset_pixel(1, 3);
+        assert!(!canvas.get_pixel(1, 3));
+    }
+
+    #[test]
+    fn should_draw_lines() {
+        let mut canvas = BrailleCanvas::new(1, 1);
+        canvas.line((0, 0), (0, 3));
+        assert_eq!(canvas.to_string(), "⡇");
+    }
+
+    #[test]
+    fn should_plot_function() {
+        let mut canvas = BrailleCanvas::new(4, 2);
+        canvas.plot(|x| x, (0.0, 1.0), (0.0, 1.0));
+        assert!(canvas.get_pixel(0, 7));
+        assert!(canvas.get_pixel(7, 0));
+        assert!(!canvas.get_pixel(0, 0));
+
+        let mut buffer = Buffer::new();
+        canvas.draw_onto(&mut buffer, 1, 0, Style::default());
+        assert_eq!(buffer.get(0, 0), Some(&Cell::default()));
+        assert_eq!(
+            buffer.get(4, 0).unwrap().ch,
+            canvas.rows()[0].chars().last().unwrap()
+        );
+    }
+}
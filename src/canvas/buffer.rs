@@ -0,0 +1,216 @@
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::canvas::style::{Cell, Style};
+
+/// Fills the column to the right of a double-width character. It's never written out.
+pub(crate) const CONTINUATION: char = '\0';
+
+/// Terminal columns taken by `ch`: 0 for control and combining characters, 2 for wide ones.
+pub(crate) fn char_width(ch: char) -> u16 {
+    ch.width().unwrap_or(0) as u16
+}
+
+pub(crate) fn text_width(text: &str) -> u16 {
+    text.width() as u16
+}
+
+/// The longest prefix of `text` that fits in `width` columns.
+pub(crate) fn truncate_to_width(text: &str, width: u16) -> String {
+    let mut used = 0;
+    text.chars()
+        .take_while(|&ch| {
+            used += char_width(ch);
+            used <= width
+        })
+        .collect()
+}
+
+/// A grid of cells that grows as cells are written to it.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Buffer {
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Clone for Buffer {
+    fn clone(&self) -> Self {
+        Self {
+            rows: self.rows.clone(),
+        }
+    }
+    /// Reuses the rows already allocated.
+    fn clone_from(&mut self, source: &Self) {
+        self.rows.clone_from(&source.rows);
+    }
+}
+
+impl Buffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn set_cell(&mut self, x: u16, y: u16, cell: impl Into<Cell>) {
+        let (x, y) = (x as usize, y as usize);
+        if self.rows.len() <= y {
+            self.rows.resize_with(y + 1, Vec::new);
+        }
+        let row = &mut self.rows[y];
+        if row.len() <= x {
+            row.resize(x + 1, Cell::default());
+        }
+        // don't leave half of a double-width character behind
+        if row[x].ch == CONTINUATION && x > 0 {
+            row[x - 1].ch = ' ';
+        }
+        if char_width(row[x].ch) > 1 && row.get(x + 1).is_some_and(|c| c.ch == CONTINUATION) {
+            row[x + 1].ch = ' ';
+        }
+        row[x] = cell.into();
+    }
+    /// Writes `ch` at `(x, y)` and returns the number of columns it takes.
+    pub(crate) fn put_char(&mut self, x: u16, y: u16, ch: char, style: Style) -> u16 {
+        let width = char_width(ch);
+        if width > 0 {
+            self.set_cell(x, y, Cell::new(ch, style));
+        }
+        if width > 1 {
+            self.set_cell(x.saturating_add(1), y, Cell::new(CONTINUATION, style));
+        }
+        width
+    }
+    pub fn get(&self, x: u16, y: u16) -> Option<&Cell> {
+        self.rows.get(y as usize)?.get(x as usize)
+    }
+    /// Writes `text` starting at `(x, y)`. Each newline continues on the next row at column `x`.
+    pub fn draw_at(&mut self, x: u16, y: u16, text: &str) {
+        self.draw_styled_at(x, y, text, Style::default());
+    }
+    pub fn draw_styled_at(&mut self, x: u16, y: u16, text: &str, style: Style) {
+        for (dy, line) in text.split('\n').enumerate() {
+            let row = y.saturating_add(dy as u16);
+            let mut column = x;
+            for ch in line.chars() {
+                let width = self.put_char(column, row, ch, style);
+                column = column.saturating_add(width);
+            }
+        }
+    }
+    pub fn draw_grid<T, R: AsRef<[T]>, C: Into<Cell>>(
+        &mut self,
+        grid: &[R],
+        to_cell: impl Fn(&T) -> C,
+    ) {
+        self.draw_grid_at(0, 0, grid, to_cell);
+    }
+    pub fn draw_grid_at<T, R: AsRef<[T]>, C: Into<Cell>>(
+        &mut self,
+        x: u16,
+        y: u16,
+        grid: &[R],
+        to_cell: impl Fn(&T) -> C,
+    ) {
+        for (dy, row) in grid.iter().enumerate() {
+            for (dx, value) in row.as_ref().iter().enumerate() {
+                self.set_cell(
+                    x.saturating_add(dx as u16),
+                    y.saturating_add(dy as u16),
+                    to_cell(value),
+                );
+            }
+        }
+    }
+    /// Lays out text the way a terminal would when printing the spans one after another.
+    pub(crate) fn from_spans(spans: &[(&str, Style)]) -> Self {
+        let mut result = Self::new();
+        let (mut x, mut y) = (0u16, 0u16);
+        for (text, style) in spans {
+            for ch in text.chars() {
+                match ch {
+                    '\n' => {
+                        x = 0;
+                        y = y.saturating_add(1);
+                    }
+                    '\r' => x = 0,
+                    ch => {
+                        let width = result.put_char(x, y, ch, *style);
+                        x = x.saturating_add(width);
+                    }
+                }
+            }
+        }
+        result
+    }
+    pub fn clear(&mut self) {
+        self.rows.clear();
+    }
+    pub fn width(&self) -> u16 {
+        self.rows.iter().map(|row| row.len()).max().unwrap_or(0) as u16
+    }
+    pub fn height(&self) -> u16 {
+        self.rows.len() as u16
+    }
+    pub fn rows(&self) -> &[Vec<Cell>] {
+        &self.rows
+    }
+    /// The buffer's characters without styling, with trailing spaces trimmed from each row.
+    pub fn to_text(&self) -> String {
+        self.rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| cell.ch)
+                    .filter(|&ch| ch != CONTINUATION)
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_compose_draws() {
+        let mut buffer = Buffer::new();
+        buffer.draw_at(2, 1, "ab\ncd");
+        buffer.set_cell(0, 0, '#');
+        buffer.set_cell(3, 2, 'X');
+        assert_eq!(buffer.to_text(), "#\n  ab\n  cX");
+        assert_eq!(buffer.width(), 4);
+        assert_eq!(buffer.height(), 3);
+        assert_eq!(buffer.get(3, 2), Some(&Cell::from('X')));
+        assert_eq!(buffer.get(9, 9), None);
+
+        buffer.clear();
+        assert_eq!(buffer.to_text(), "");
+    }
+
+    #[test]
+    fn should_lay_out_wide_characters() {
+        let mut buffer = Buffer::new();
+        buffer.draw_at(0, 0, "日本|\nab|");
+        assert_eq!(buffer.to_text(), "日本|\nab|");
+        assert_eq!(buffer.get(4, 0), Some(&Cell::from('|')));
+        assert_eq!(buffer.width(), 5);
+
+        buffer.set_cell(1, 0, 'x');
+        assert_eq!(buffer.to_text(), " x本|\nab|");
+        assert_eq!(truncate_to_width("日本語", 5), "日本");
+        assert_eq!(text_width("日本語"), 6);
+    }
+
+    #[test]
+    fn should_draw_grid() {
+        let grid = vec![vec![true, false], vec![false, true]];
+        let mut buffer = Buffer::new();
+        buffer.draw_grid(&grid, |&wall| if wall { '#' } else { '.' });
+        assert_eq!(buffer.to_text(), "#.\n.#");
+
+        let chars: Vec<Vec<char>> = vec!["ab".chars().collect(), "cd".chars().collect()];
+        buffer.draw_grid_at(1, 1, &chars, |&ch| (ch, Style::new().bold()));
+        assert_eq!(buffer.to_text(), "#.\n.ab\n cd");
+        assert!(buffer.get(1, 1).unwrap().style.bold);
+    }
+}
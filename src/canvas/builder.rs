@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use crate::canvas::backend::{restore_terminal_on_panic, Output, TerminalBackend};
+use crate::canvas::Canvas;
+
+/// Configures how a terminal `Canvas` treats the terminal. The defaults match `Canvas::new`.
+#[derive(Clone, Debug)]
+pub struct CanvasBuilder {
+    hide_cursor: bool,
+    raw_mode: bool,
+    clear_on_start: bool,
+    clear_on_drop: bool,
+    output: Output,
+    delay: Option<Duration>,
+    frame_interval: Option<Duration>,
+    restore_on_panic: bool,
+}
+
+impl Default for CanvasBuilder {
+    fn default() -> Self {
+        Self {
+            hide_cursor: true,
+            raw_mode: false,
+            clear_on_start: true,
+            clear_on_drop: false,
+            output: Output::Stdout,
+            delay: None,
+            frame_interval: None,
+            restore_on_panic: false,
+        }
+    }
+}
+
+impl CanvasBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_hidden_cursor(mut self, hide_cursor: bool) -> Self {
+        self.hide_cursor = hide_cursor;
+        self
+    }
+    /// Enables raw mode right away instead of on the first `poll_event`.
+    pub fn with_raw_mode(mut self, raw_mode: bool) -> Self {
+        self.raw_mode = raw_mode;
+        self
+    }
+    /// When disabled, drawing starts at the current cursor row and earlier output is kept.
+    pub fn with_clear_on_start(mut self, clear_on_start: bool) -> Self {
+        self.clear_on_start = clear_on_start;
+        self
+    }
+    pub fn with_clear_on_drop(mut self, clear_on_drop: bool) -> Self {
+        self.clear_on_drop = clear_on_drop;
+        self
+    }
+    pub fn with_output(mut self, output: Output) -> Self {
+        self.output = output;
+        self
+    }
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+    pub fn with_frame_interval(mut self, interval: Duration) -> Self {
+        self.frame_interval = Some(interval);
+        self
+    }
+    pub fn with_target_fps(self, fps: u32) -> Self {
+        self.with_frame_interval(Duration::from_secs(1) / fps.max(1))
+    }
+    /// Restores the terminal before the panic message is printed. See `restore_terminal_on_panic`.
+    pub fn with_restore_on_panic(mut self, restore_on_panic: bool) -> Self {
+        self.restore_on_panic = restore_on_panic;
+        self
+    }
+    pub fn build(self) -> std::io::Result<Canvas> {
+        if self.restore_on_panic {
+            restore_terminal_on_panic(self.output);
+        }
+        let mut backend = TerminalBackend::with_output(self.output);
+        backend.hide_cursor = self.hide_cursor;
+        backend.clear_on_start = self.clear_on_start;
+        backend.clear_on_drop = self.clear_on_drop;
+        let mut canvas = Canvas::with_backend(backend)?;
+        if let Some(delay) = self.delay {
+            canvas = canvas.with_delay(delay);
+        }
+        if let Some(interval) = self.frame_interval {
+            canvas = canvas.with_frame_interval(interval);
+        }
+        canvas.set_raw_mode(self.raw_mode)?;
+        Ok(canvas)
+    }
+}
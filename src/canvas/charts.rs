@@ -0,0 +1,137 @@
+use crate::canvas::buffer::{text_width, Buffer};
+use crate::canvas::style::Style;
+use crate::pretty_print::make_bins;
+
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+const BAR_EIGHTHS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// One character per value, scaled between the smallest and largest (finite) value.
+pub fn sparkline(values: &[f64]) -> String {
+    let finite = values.iter().copied().filter(|v| v.is_finite());
+    let min = finite.clone().fold(f64::INFINITY, f64::min);
+    let max = finite.fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|&value| {
+            if !value.is_finite() {
+                ' '
+            } else if max > min {
+                let level = ((value - min) / (max - min) * 7.0).round() as usize;
+                SPARK_LEVELS[level.min(7)]
+            } else {
+                SPARK_LEVELS[3]
+            }
+        })
+        .collect()
+}
+
+/// A horizontal bar of `value / max` times `width` cells, using eighth blocks for the remainder.
+fn bar(value: f64, max: f64, width: u16) -> String {
+    if max <= 0.0 || value <= 0.0 {
+        return String::new();
+    }
+    let eighths = ((value / max).min(1.0) * width as f64 * 8.0).round() as usize;
+    let mut result = "█".repeat(eighths / 8);
+    let remainder = eighths % 8;
+    if remainder > 0 {
+        result.push(BAR_EIGHTHS[remainder]);
+    }
+    result
+}
+
+/// Draws one labelled row per entry, with the bars scaled so the largest value fills `width`.
+pub fn bar_chart(
+    buffer: &mut Buffer,
+    (x, y): (u16, u16),
+    rows: &[(&str, f64)],
+    width: u16,
+    style: Style,
+) {
+    let label_width = rows
+        .iter()
+        .map(|(label, _)| text_width(label))
+        .max()
+        .unwrap_or(0);
+    let max = rows.iter().map(|(_, value)| *value).fold(0.0, f64::max);
+    // rows past the last one are clipped
+    for (i, (label, value)) in (0..=u16::MAX).zip(rows) {
+        let Some(row) = y.checked_add(i) else {
+            break;
+        };
+        let padding = " ".repeat((label_width - text_width(label)) as usize);
+        let label = format!("{}{} ", padding, label);
+        buffer.draw_at(x, row, &label);
+        let bar_x = x.saturating_add(text_width(&label));
+        let bar = bar(*value, max, width);
+        buffer.draw_styled_at(bar_x, row, &bar, style);
+        let value_x = bar_x.saturating_add(text_width(&bar)).saturating_add(1);
+        buffer.draw_at(value_x, row, &value.to_string());
+    }
+}
+
+/// Bins `values` into `bins` equal-width buckets and draws them as a bar chart of counts.
+pub fn histogram(
+    buffer: &mut Buffer,
+    position: (u16, u16),
+    values: &[f64],
+    bins: usize,
+    width: u16,
+    style: Style,
+) {
+    let labels: Vec<(String, f64)> = make_bins(values, bins)
+        .into_iter()
+        .map(|bin| (format!("{:.3}", bin.start), bin.count as f64))
+        .collect();
+    let rows: Vec<(&str, f64)> = labels
+        .iter()
+        .map(|(label, count)| (label.as_str(), *count))
+        .collect();
+    bar_chart(buffer, position, &rows, width, style);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_draw_sparkline() {
+        assert_eq!(sparkline(&[1.0, 2.0, 3.0, 8.0]), "▁▂▃█");
+        assert_eq!(sparkline(&[5.0, 5.0]), "▄▄");
+        assert_eq!(sparkline(&[0.0, f64::NAN, 1.0]), "▁ █");
+        assert_eq!(sparkline(&[]), "");
+    }
+
+    #[test]
+    fn should_draw_bar_chart() {
+        let mut buffer = Buffer::new();
+        bar_chart(
+            &mut buffer,
+            (0, 0),
+            &[("a", 4.0), ("bb", 1.0), ("c", 0.0)],
+            4,
+            Style::default(),
+        );
+        assert_eq!(buffer.to_text(), " a ████ 4\nbb █ 1\n c  0");
+        assert_eq!(bar(3.0, 8.0, 2), "▊");
+
+        let mut buffer = Buffer::new();
+        let rows = [("a", 1.0), ("b", 2.0)];
+        bar_chart(&mut buffer, (0, u16::MAX), &rows, 2, Style::default());
+        assert_eq!(buffer.rows().len(), u16::MAX as usize + 1);
+        assert_eq!(buffer.to_text().trim_start(), "a █ 1");
+    }
+
+    #[test]
+    fn should_draw_histogram() {
+        let mut buffer = Buffer::new();
+        histogram(
+            &mut buffer,
+            (0, 0),
+            &[0.0, 0.1, 0.2, 1.0],
+            2,
+            3,
+            Style::default(),
+        );
+        assert_eq!(buffer.to_text(), "0.000 ███ 3\n0.500 █ 1");
+    }
+}
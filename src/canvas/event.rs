@@ -0,0 +1,60 @@
+use crossterm::event::{self as ct, KeyEventKind};
+pub use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    Key(KeyEvent),
+    /// Esc, `q` or Ctrl+C were pressed.
+    Quit,
+    /// The terminal was resized to `(columns, rows)`. The canvas has already been cleared.
+    Resize(u16, u16),
+}
+
+impl Event {
+    pub fn is_quit(&self) -> bool {
+        matches!(self, Event::Quit)
+    }
+    pub fn key_code(&self) -> Option<KeyCode> {
+        match self {
+            Event::Key(key) => Some(key.code),
+            _ => None,
+        }
+    }
+    pub(crate) fn from_crossterm(event: ct::Event) -> Option<Self> {
+        match event {
+            ct::Event::Key(key) if key.kind != KeyEventKind::Release => Some(match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => Event::Quit,
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Event::Quit,
+                _ => Event::Key(key),
+            }),
+            ct::Event::Resize(columns, rows) => Some(Event::Resize(columns, rows)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_map_quit_keys() {
+        let key = |code, modifiers| ct::Event::Key(KeyEvent::new(code, modifiers));
+        assert_eq!(
+            Event::from_crossterm(key(KeyCode::Esc, KeyModifiers::NONE)),
+            Some(Event::Quit)
+        );
+        assert_eq!(
+            Event::from_crossterm(key(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Some(Event::Quit)
+        );
+        let right = Event::from_crossterm(key(KeyCode::Right, KeyModifiers::NONE)).unwrap();
+        assert_eq!(right.key_code(), Some(KeyCode::Right));
+        assert!(!right.is_quit());
+        assert_eq!(
+            Event::from_crossterm(ct::Event::Resize(80, 24)),
+            Some(Event::Resize(80, 24))
+        );
+        assert_eq!(Event::from_crossterm(ct::Event::FocusGained), None);
+    }
+}
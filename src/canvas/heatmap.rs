@@ -0,0 +1,167 @@
+use crate::canvas::buffer::{text_width, Buffer};
+use crate::canvas::style::{Cell, Color, Style};
+use crate::common::AsF64;
+
+const SHADES: [char; 5] = [' ', '░', '▒', '▓', '█'];
+const LEGEND_WIDTH: u16 = 10;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HeatmapMode {
+    /// Full blocks colored from blue (low) to red (high).
+    #[default]
+    Color,
+    /// Shading characters, for terminals or recordings without color.
+    Shade,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HeatmapOptions {
+    mode: HeatmapMode,
+    range: Option<(f64, f64)>,
+    legend: bool,
+}
+
+impl HeatmapOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_mode(mut self, mode: HeatmapMode) -> Self {
+        self.mode = mode;
+        self
+    }
+    /// Fixes the scale instead of using the min and max of the values.
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+    /// Adds a `min <scale> max` line below the map.
+    pub fn with_legend(mut self) -> Self {
+        self.legend = true;
+        self
+    }
+    fn cell(&self, fraction: f64) -> Cell {
+        let fraction = fraction.clamp(0.0, 1.0);
+        match self.mode {
+            HeatmapMode::Color => Cell::new('█', Style::new().fg(gradient(fraction))),
+            HeatmapMode::Shade => {
+                Cell::from(SHADES[(fraction * (SHADES.len() - 1) as f64).round() as usize])
+            }
+        }
+    }
+}
+
+fn gradient(fraction: f64) -> Color {
+    // blue -> cyan -> green -> yellow -> red
+    const STOPS: [(u8, u8, u8); 5] = [
+        (0, 0, 255),
+        (0, 255, 255),
+        (0, 255, 0),
+        (255, 255, 0),
+        (255, 0, 0),
+    ];
+    let scaled = fraction * (STOPS.len() - 1) as f64;
+    let i = (scaled.floor() as usize).min(STOPS.len() - 2);
+    let t = scaled - i as f64;
+    let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+    let ((r0, g0, b0), (r1, g1, b1)) = (STOPS[i], STOPS[i + 1]);
+    Color::Rgb {
+        r: lerp(r0, r1),
+        g: lerp(g0, g1),
+        b: lerp(b0, b1),
+    }
+}
+
+/// Draws one cell per value, scaled between the smallest and largest finite value (or the
+/// configured range). Non-finite values are left blank.
+pub fn heatmap<T: AsF64, R: AsRef<[T]>>(
+    buffer: &mut Buffer,
+    (x, y): (u16, u16),
+    grid: &[R],
+    options: &HeatmapOptions,
+) {
+    let (min, max) = options.range.unwrap_or_else(|| {
+        grid.iter()
+            .flat_map(|row| row.as_ref().iter().map(|v| v.as_f64()))
+            .filter(|v| v.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+                (min.min(v), max.max(v))
+            })
+    });
+    let span = max - min;
+    // values past the last row or column are clipped
+    for (dy, row) in (0..=u16::MAX).zip(grid) {
+        for (dx, value) in (0..=u16::MAX).zip(row.as_ref()) {
+            let value = value.as_f64();
+            let cell = if !value.is_finite() {
+                Cell::default()
+            } else if span > 0.0 {
+                options.cell((value - min) / span)
+            } else {
+                options.cell(0.5)
+            };
+            if let (Some(column), Some(line)) = (x.checked_add(dx), y.checked_add(dy)) {
+                buffer.set_cell(column, line, cell);
+            }
+        }
+    }
+    if options.legend && min.is_finite() {
+        let row = y.saturating_add(u16::try_from(grid.len()).unwrap_or(u16::MAX));
+        let low = format!("{} ", min);
+        buffer.draw_at(x, row, &low);
+        let start = x.saturating_add(text_width(&low));
+        for i in 0..LEGEND_WIDTH {
+            let cell = options.cell(i as f64 / (LEGEND_WIDTH - 1) as f64);
+            if let Some(column) = start.checked_add(i) {
+                buffer.set_cell(column, row, cell);
+            }
+        }
+        buffer.draw_at(
+            start.saturating_add(LEGEND_WIDTH),
+            row,
+            &format!(" {}", max),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_shade_values() {
+        let mut buffer = Buffer::new();
+        let grid = vec![vec![0, 1, 2], vec![3, 4, 4]];
+        let options = HeatmapOptions::new()
+            .with_mode(HeatmapMode::Shade)
+            .with_legend();
+        heatmap(&mut buffer, (0, 0), &grid, &options);
+        assert_eq!(buffer.to_text(), " ░▒\n▓██\n0   ░░▒▒▓▓██ 4");
+    }
+
+    #[test]
+    fn should_color_values() {
+        let mut buffer = Buffer::new();
+        let grid = [[0.0, f64::NAN, 10.0]];
+        heatmap(&mut buffer, (0, 0), &grid, &HeatmapOptions::new());
+        assert_eq!(
+            buffer.get(0, 0).unwrap().style.fg,
+            Some(Color::Rgb { r: 0, g: 0, b: 255 })
+        );
+        assert_eq!(buffer.get(1, 0), Some(&Cell::default()));
+        assert_eq!(
+            buffer.get(2, 0).unwrap().style.fg,
+            Some(Color::Rgb { r: 255, g: 0, b: 0 })
+        );
+    }
+
+    #[test]
+    fn should_clip_at_the_last_column() {
+        let mut buffer = Buffer::new();
+        let options = HeatmapOptions::new()
+            .with_mode(HeatmapMode::Shade)
+            .with_legend();
+        heatmap(&mut buffer, (u16::MAX - 1, 0), &[[0, 2, 4, 4]], &options);
+        assert_eq!(buffer.rows()[0].len(), u16::MAX as usize + 1);
+        assert_eq!(buffer.get(u16::MAX, 0).unwrap().ch, '▒');
+    }
+}
@@ -0,0 +1,394 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+use crossterm::style::PrintStyledContent;
+use crossterm::{cursor, terminal, QueueableCommand};
+
+pub use backend::{restore_terminal_on_panic, Backend, HeadlessBackend, Output, TerminalBackend};
+pub use braille::BrailleCanvas;
+pub use buffer::Buffer;
+use buffer::CONTINUATION;
+pub use builder::CanvasBuilder;
+pub use event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use pacing::FramePacer;
+pub use recording::{RecordedFrame, Recording};
+pub use renderer::Renderer;
+pub use search_view::{SearchStyles, SearchView};
+pub use sprite::{Sprite, SpriteId};
+pub use style::{Cell, Color, Style};
+pub use viewport::Viewport;
+use widgets::StatusBar;
+
+mod backend;
+mod braille;
+mod buffer;
+mod builder;
+pub mod charts;
+mod event;
+pub mod heatmap;
+mod pacing;
+mod recording;
+mod renderer;
+mod search_view;
+pub mod shapes;
+mod sprite;
+mod style;
+mod viewport;
+pub mod widgets;
+
+pub struct Canvas<B: Backend = TerminalBackend> {
+    backend: B,
+    pacer: FramePacer,
+    buffer: Buffer,
+    /// `buffer` plus sprites and the status bar, kept so each frame reuses its rows.
+    frame: Buffer,
+    raw_mode: bool,
+    size: (u16, u16),
+    on_resize: Option<Box<dyn FnMut(u16, u16) + Send>>,
+    sprites: BTreeMap<SpriteId, Sprite>,
+    next_sprite_id: usize,
+    recording: Option<(Instant, Recording)>,
+    status_bar: Option<StatusBar>,
+}
+
+impl Canvas {
+    pub fn new() -> std::io::Result<Self> {
+        Self::with_backend(TerminalBackend::new())
+    }
+    pub fn builder() -> CanvasBuilder {
+        CanvasBuilder::new()
+    }
+}
+
+impl Canvas<HeadlessBackend> {
+    pub fn headless(size: (u16, u16)) -> Self {
+        Self::with_backend(HeadlessBackend::new(size)).expect("headless backend can't fail")
+    }
+}
+
+impl<B: Backend> Canvas<B> {
+    pub fn with_backend(mut backend: B) -> std::io::Result<Self> {
+        backend.init()?;
+        Ok(Self {
+            size: backend.size().unwrap_or_default(),
+            backend,
+            pacer: FramePacer::default(),
+            buffer: Buffer::new(),
+            frame: Buffer::new(),
+            raw_mode: false,
+            on_resize: None,
+            sprites: BTreeMap::new(),
+            next_sprite_id: 0,
+            recording: None,
+            status_bar: None,
+        })
+    }
+    /// Sleeps for `delay` after every frame is shown.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.pacer.set_delay(Some(delay));
+        self
+    }
+    /// Frames are shown at most once per `interval`. Time spent between draws counts towards it.
+    pub fn with_frame_interval(mut self, interval: Duration) -> Self {
+        self.pacer.set_interval(Some(interval));
+        self
+    }
+    pub fn with_target_fps(self, fps: u32) -> Self {
+        self.with_frame_interval(Duration::from_secs(1) / fps.max(1))
+    }
+    /// Called with the new `(columns, rows)` after the canvas clears itself on a resize.
+    pub fn with_resize_handler(mut self, on_resize: impl FnMut(u16, u16) + Send + 'static) -> Self {
+        self.on_resize = Some(Box::new(on_resize));
+        self
+    }
+    pub fn size(&self) -> (u16, u16) {
+        self.size
+    }
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+    pub fn backend_mut(&mut self) -> &mut B {
+        &mut self.backend
+    }
+    pub fn draw(&mut self, text: &str) -> std::io::Result<()> {
+        let output = if self.raw_mode {
+            text.replace('\n', "\r\n")
+        } else {
+            text.to_string()
+        };
+        self.render(
+            |out| out.write_all(output.as_bytes()),
+            &Buffer::from_spans(&[(text, Style::default())]),
+        )
+    }
+    pub fn draw_styled(&mut self, text: &str, style: Style) -> std::io::Result<()> {
+        self.draw_spans(&[(text, style)])
+    }
+    pub fn draw_colored(&mut self, text: &str, color: Color) -> std::io::Result<()> {
+        self.draw_styled(text, Style::new().fg(color))
+    }
+    /// Draws a single frame made of differently styled pieces of text.
+    pub fn draw_spans(&mut self, spans: &[(&str, Style)]) -> std::io::Result<()> {
+        let raw_mode = self.raw_mode;
+        self.render(
+            |out| {
+                for (text, style) in spans {
+                    if raw_mode {
+                        out.queue(PrintStyledContent(style.apply(text.replace('\n', "\r\n"))))?;
+                    } else {
+                        out.queue(PrintStyledContent(style.apply(text)))?;
+                    }
+                }
+                Ok(())
+            },
+            &Buffer::from_spans(spans),
+        )
+    }
+    pub fn draw_at(&mut self, x: u16, y: u16, text: &str) {
+        self.buffer.draw_at(x, y, text);
+    }
+    pub fn draw_styled_at(&mut self, x: u16, y: u16, text: &str, style: Style) {
+        self.buffer.draw_styled_at(x, y, text, style);
+    }
+    pub fn set_cell(&mut self, x: u16, y: u16, cell: impl Into<Cell>) {
+        self.buffer.set_cell(x, y, cell);
+    }
+    pub fn draw_grid<T, R: AsRef<[T]>, C: Into<Cell>>(
+        &mut self,
+        grid: &[R],
+        to_cell: impl Fn(&T) -> C,
+    ) {
+        self.buffer.draw_grid(grid, to_cell);
+    }
+    pub fn draw_char_grid<R: AsRef<[char]>>(&mut self, grid: &[R]) {
+        self.buffer.draw_grid(grid, |&ch| ch);
+    }
+    pub fn draw_braille(&mut self, x: u16, y: u16, braille: &BrailleCanvas, style: Style) {
+        braille.draw_onto(&mut self.buffer, x, y, style);
+    }
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+    pub fn buffer_mut(&mut self) -> &mut Buffer {
+        &mut self.buffer
+    }
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+    }
+    /// Sprites are drawn on top of the buffer in the order they were added.
+    pub fn add_sprite(&mut self, sprite: Sprite) -> SpriteId {
+        let id = SpriteId(self.next_sprite_id);
+        self.next_sprite_id += 1;
+        self.sprites.insert(id, sprite);
+        id
+    }
+    pub fn move_sprite(&mut self, id: SpriteId, x: u16, y: u16) -> bool {
+        match self.sprites.get_mut(&id) {
+            Some(sprite) => {
+                sprite.move_to(x, y);
+                true
+            }
+            None => false,
+        }
+    }
+    pub fn remove_sprite(&mut self, id: SpriteId) -> Option<Sprite> {
+        self.sprites.remove(&id)
+    }
+    pub fn sprite(&self, id: SpriteId) -> Option<&Sprite> {
+        self.sprites.get(&id)
+    }
+    pub fn sprite_mut(&mut self, id: SpriteId) -> Option<&mut Sprite> {
+        self.sprites.get_mut(&id)
+    }
+    /// Pins a line of text to the bottom row of the terminal, e.g. the current a* step.
+    pub fn set_status(&mut self, text: &str) {
+        match self.status_bar.as_mut() {
+            Some(status_bar) => status_bar.text = text.to_string(),
+            None => self.status_bar = Some(StatusBar::new(text)),
+        }
+    }
+    pub fn set_status_bar(&mut self, status_bar: Option<StatusBar>) {
+        self.status_bar = status_bar;
+    }
+    /// Draws everything written with `draw_at`/`set_cell` plus the sprites as one frame. The
+    /// buffer is kept, so the next frame only needs to redraw what changed; call `clear` to
+    /// start from scratch.
+    pub fn present(&mut self) -> std::io::Result<()> {
+        // swapped out while it's drawn, since rendering borrows the canvas
+        let mut frame = std::mem::take(&mut self.frame);
+        self.compose_into(&mut frame);
+        let result = self.render(|out| write_buffer(out, &frame), &frame);
+        std::mem::swap(&mut self.frame, &mut frame);
+        result
+    }
+    /// Moves the canvas to a render thread so that drawing and frame pacing no longer block
+    /// the caller. Frames are sent as buffers and drawn with `present`.
+    pub fn spawn_renderer(self) -> Renderer<B> {
+        Renderer::spawn(self)
+    }
+    /// Starts capturing every frame drawn from now on. Any previous recording is discarded.
+    pub fn record(&mut self) {
+        self.recording = Some((Instant::now(), Recording::new(self.size)));
+    }
+    pub fn stop_recording(&mut self) -> Option<Recording> {
+        self.recording.take().map(|(_, recording)| recording)
+    }
+    pub fn recording(&self) -> Option<&Recording> {
+        self.recording.as_ref().map(|(_, recording)| recording)
+    }
+    fn compose_into(&self, frame: &mut Buffer) {
+        frame.clone_from(&self.buffer);
+        for sprite in self.sprites.values() {
+            sprite.draw_onto(frame);
+        }
+        if let Some(status_bar) = &self.status_bar {
+            let (width, height) = self.size;
+            let row = if height > 0 {
+                height - 1
+            } else {
+                frame.height()
+            };
+            let width = if width > 0 { width } else { frame.width() };
+            status_bar.draw(frame, row, width);
+        }
+    }
+    /// Waits up to `timeout` for a key press. The terminal is switched to raw mode on the first
+    /// call and restored when the canvas is dropped.
+    pub fn poll_event(&mut self, timeout: Duration) -> std::io::Result<Option<Event>> {
+        self.set_raw_mode(true)?;
+        let event = match self.backend.poll_event(timeout)? {
+            Some(event) => Event::from_crossterm(event),
+            None => return Ok(None),
+        };
+        if let Some(Event::Resize(columns, rows)) = event {
+            self.handle_resize((columns, rows))?;
+        }
+        Ok(event)
+    }
+    pub fn set_raw_mode(&mut self, enabled: bool) -> std::io::Result<()> {
+        if self.raw_mode != enabled {
+            self.backend.set_raw_mode(enabled)?;
+            self.raw_mode = enabled;
+        }
+        Ok(())
+    }
+    fn handle_resize(&mut self, size: (u16, u16)) -> std::io::Result<()> {
+        if size == self.size {
+            return Ok(());
+        }
+        self.size = size;
+        self.backend.clear()?;
+        if let Some(on_resize) = self.on_resize.as_mut() {
+            on_resize(size.0, size.1);
+        }
+        Ok(())
+    }
+    fn render(
+        &mut self,
+        write: impl FnOnce(&mut Vec<u8>) -> std::io::Result<()>,
+        cells: &Buffer,
+    ) -> std::io::Result<()> {
+        if let Ok(size) = self.backend.size() {
+            self.handle_resize(size)?;
+        }
+        let mut out = vec![];
+        out.queue(terminal::Clear(terminal::ClearType::FromCursorDown))?;
+        out.queue(cursor::SavePosition)?;
+        write(&mut out)?;
+        out.queue(cursor::RestorePosition)?;
+        self.pacer.wait();
+        if let Some((start, recording)) = self.recording.as_mut() {
+            recording.frames.push(RecordedFrame {
+                at: start.elapsed(),
+                output: String::from_utf8_lossy(&out).into_owned(),
+                cells: cells.clone(),
+            });
+        }
+        self.backend.write_frame(&out, cells)?;
+        self.pacer.delay();
+        Ok(())
+    }
+}
+
+fn write_buffer(stdout: &mut impl Write, buffer: &Buffer) -> std::io::Result<()> {
+    for (y, row) in buffer.rows().iter().enumerate() {
+        if y > 0 {
+            stdout.queue(cursor::MoveToNextLine(1))?;
+        }
+        let mut cells = row.iter().filter(|cell| cell.ch != CONTINUATION).peekable();
+        while let Some(first) = cells.next() {
+            let mut run = String::from(first.ch);
+            while let Some(cell) = cells.next_if(|cell| cell.style == first.style) {
+                run.push(cell.ch);
+            }
+            stdout.queue(PrintStyledContent(first.style.apply(run)))?;
+        }
+    }
+    Ok(())
+}
+
+impl<B: Backend> Drop for Canvas<B> {
+    fn drop(&mut self) {
+        if self.raw_mode {
+            self.backend.set_raw_mode(false).unwrap();
+        }
+        self.backend.restore().unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_present_buffer_with_sprites_and_status() {
+        let mut canvas = Canvas::headless((6, 3));
+        canvas.draw_at(0, 0, "......");
+        let sprite = canvas.add_sprite(Sprite::new(1, 0, "@"));
+        canvas.set_status("ok");
+        canvas.present().unwrap();
+        assert_eq!(canvas.backend().last_text(), ".@....\n\nok");
+
+        canvas.move_sprite(sprite, 3, 0);
+        canvas.present().unwrap();
+        assert_eq!(canvas.backend().last_text(), "...@..\n\nok");
+        assert_eq!(canvas.backend().frames().len(), 2);
+    }
+
+    #[test]
+    fn should_poll_events_and_handle_resize() {
+        let resized = std::sync::Arc::new(std::sync::Mutex::new(None));
+        let on_resize = resized.clone();
+        let mut canvas = Canvas::headless((10, 5)).with_resize_handler(move |columns, rows| {
+            *on_resize.lock().unwrap() = Some((columns, rows));
+        });
+        assert_eq!(canvas.poll_event(Duration::ZERO).unwrap(), None);
+        assert!(canvas.backend().is_raw_mode());
+
+        canvas
+            .backend_mut()
+            .push_event(crossterm::event::Event::Resize(20, 8));
+        assert_eq!(
+            canvas.poll_event(Duration::ZERO).unwrap(),
+            Some(Event::Resize(20, 8))
+        );
+        assert_eq!(canvas.size(), (20, 8));
+        assert_eq!(*resized.lock().unwrap(), Some((20, 8)));
+
+        canvas.backend_mut().resize((30, 9));
+        canvas.draw("hi").unwrap();
+        assert_eq!(canvas.size(), (30, 9));
+        assert_eq!(canvas.backend().last_text(), "hi");
+    }
+
+    #[test]
+    fn should_render_on_background_thread() {
+        let renderer = Canvas::headless((4, 1)).spawn_renderer();
+        let mut frame = Buffer::new();
+        frame.draw_at(0, 0, "ab");
+        assert!(renderer.send(frame));
+        let canvas = renderer.finish().unwrap();
+        assert_eq!(canvas.backend().last_text(), "ab");
+    }
+}
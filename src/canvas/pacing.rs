@@ -0,0 +1,74 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Keeps frames at least `interval` apart, sleeping only for whatever part of the interval the
+/// caller didn't already spend producing the frame. `delay` is slept in full after every frame.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct FramePacer {
+    interval: Option<Duration>,
+    delay: Option<Duration>,
+    last_frame: Option<Instant>,
+}
+
+impl FramePacer {
+    pub(crate) fn set_interval(&mut self, interval: Option<Duration>) {
+        self.interval = interval;
+    }
+    pub(crate) fn set_delay(&mut self, delay: Option<Duration>) {
+        self.delay = delay;
+    }
+    pub(crate) fn remaining(&self) -> Duration {
+        match (self.interval, self.last_frame) {
+            (Some(interval), Some(last_frame)) => interval.saturating_sub(last_frame.elapsed()),
+            _ => Duration::ZERO,
+        }
+    }
+    pub(crate) fn wait(&mut self) {
+        let remaining = self.remaining();
+        if !remaining.is_zero() {
+            thread::sleep(remaining);
+        }
+        self.last_frame = Some(Instant::now());
+    }
+    pub(crate) fn delay(&self) {
+        if let Some(delay) = self.delay {
+            thread::sleep(delay);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_only_sleep_the_remainder() {
+        let mut pacer = FramePacer::default();
+        pacer.set_interval(Some(Duration::from_millis(100)));
+        pacer.wait();
+        thread::sleep(Duration::from_millis(60));
+        let remaining = pacer.remaining();
+        assert!(remaining <= Duration::from_millis(40), "{:?}", remaining);
+        assert!(remaining > Duration::ZERO);
+
+        let start = Instant::now();
+        pacer.wait();
+        assert!(start.elapsed() < Duration::from_millis(60));
+
+        thread::sleep(Duration::from_millis(120));
+        assert_eq!(pacer.remaining(), Duration::ZERO);
+    }
+
+    #[test]
+    fn should_sleep_the_whole_delay() {
+        let mut pacer = FramePacer::default();
+        pacer.set_delay(Some(Duration::from_millis(30)));
+        pacer.wait();
+        thread::sleep(Duration::from_millis(30));
+        assert_eq!(pacer.remaining(), Duration::ZERO);
+
+        let start = Instant::now();
+        pacer.delay();
+        assert!(start.elapsed() >= Duration::from_millis(30));
+    }
+}
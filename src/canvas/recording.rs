@@ -0,0 +1,252 @@
+use std::path::Path;
+use std::time::Duration;
+
+use crate::canvas::buffer::Buffer;
+
+pub struct RecordedFrame {
+    pub at: Duration,
+    /// The bytes written to the terminal for this frame, including escape sequences.
+    pub output: String,
+    pub cells: Buffer,
+}
+
+/// Frames captured by `Canvas::record`, exportable as an asciinema cast or (with the `gif`
+/// feature) an animated GIF.
+pub struct Recording {
+    pub(crate) size: (u16, u16),
+    pub(crate) frames: Vec<RecordedFrame>,
+}
+
+impl Recording {
+    pub(crate) fn new(size: (u16, u16)) -> Self {
+        Self {
+            size,
+            frames: vec![],
+        }
+    }
+    pub fn frames(&self) -> &[RecordedFrame] {
+        &self.frames
+    }
+    fn dimensions(&self) -> (u16, u16) {
+        self.frames
+            .iter()
+            .fold(self.size, |(width, height), frame| {
+                (
+                    width.max(frame.cells.width()),
+                    height.max(frame.cells.height()),
+                )
+            })
+    }
+    /// Asciicast v2, see https://docs.asciinema.org/manual/asciicast/v2/
+    pub fn to_asciicast(&self) -> String {
+        let (width, height) = self.dimensions();
+        let mut result = format!(
+            "{{\"version\": 2, \"width\": {}, \"height\": {}}}\n",
+            width.max(1),
+            height.max(1)
+        );
+        for frame in &self.frames {
+            result.push_str(&format!(
+                "[{:.6}, \"o\", {}]\n",
+                frame.at.as_secs_f64(),
+                json_string(&frame.output)
+            ));
+        }
+        result
+    }
+    pub fn save_asciicast(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_asciicast())
+    }
+    /// Each cell becomes a solid block: its foreground color (white by default) when it holds a
+    /// visible character, its background color (black by default) otherwise. Glyphs themselves
+    /// are not rendered, which suits grid visualizations where the color carries the meaning.
+    #[cfg(feature = "gif")]
+    pub fn write_gif(&self, writer: impl std::io::Write) -> std::io::Result<()> {
+        gif_export::write(self, writer)
+    }
+    #[cfg(feature = "gif")]
+    pub fn save_gif(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        self.write_gif(std::io::BufWriter::new(std::fs::File::create(path)?))
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut result = String::with_capacity(text.len() + 2);
+    result.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => result.push(ch),
+        }
+    }
+    result.push('"');
+    result
+}
+
+#[cfg(feature = "gif")]
+mod gif_export {
+    use std::io::Write;
+
+    use crate::canvas::recording::Recording;
+    use crate::canvas::style::Color;
+
+    const CELL_WIDTH: u16 = 4;
+    const CELL_HEIGHT: u16 = 8;
+
+    pub(super) fn write(recording: &Recording, writer: impl Write) -> std::io::Result<()> {
+        let (columns, rows) = recording.dimensions();
+        let (Some(width), Some(height)) = (
+            columns.max(1).checked_mul(CELL_WIDTH),
+            rows.max(1).checked_mul(CELL_HEIGHT),
+        ) else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("{}x{} cells is too large for a GIF", columns, rows),
+            ));
+        };
+        let mut encoder =
+            gif::Encoder::new(writer, width, height, &[]).map_err(std::io::Error::other)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(std::io::Error::other)?;
+        for (i, frame) in recording.frames.iter().enumerate() {
+            let mut pixels = vec![0u8; width as usize * height as usize * 3];
+            for (y, row) in frame.cells.rows().iter().enumerate() {
+                for (x, cell) in row.iter().enumerate() {
+                    let color = if cell.ch.is_whitespace() {
+                        cell.style.bg.map_or([0, 0, 0], to_rgb)
+                    } else {
+                        cell.style.fg.map_or([255, 255, 255], to_rgb)
+                    };
+                    for py in 0..CELL_HEIGHT as usize {
+                        for px in 0..CELL_WIDTH as usize {
+                            let offset = ((y * CELL_HEIGHT as usize + py) * width as usize
+                                + x * CELL_WIDTH as usize
+                                + px)
+                                * 3;
+                            pixels[offset..offset + 3].copy_from_slice(&color);
+                        }
+                    }
+                }
+            }
+            let mut gif_frame = gif::Frame::from_rgb_speed(width, height, &pixels, 10);
+            let next_at = recording
+                .frames
+                .get(i + 1)
+                .map_or(frame.at + std::time::Duration::from_secs(1), |f| f.at);
+            gif_frame.delay =
+                ((next_at - frame.at).as_millis() / 10).clamp(1, u16::MAX as u128) as u16;
+            encoder
+                .write_frame(&gif_frame)
+                .map_err(std::io::Error::other)?;
+        }
+        Ok(())
+    }
+
+    fn to_rgb(color: Color) -> [u8; 3] {
+        match color {
+            Color::Reset | Color::Black => [0, 0, 0],
+            Color::DarkGrey => [128, 128, 128],
+            Color::Red => [255, 85, 85],
+            Color::DarkRed => [170, 0, 0],
+            Color::Green => [85, 255, 85],
+            Color::DarkGreen => [0, 170, 0],
+            Color::Yellow => [255, 255, 85],
+            Color::DarkYellow => [170, 85, 0],
+            Color::Blue => [85, 85, 255],
+            Color::DarkBlue => [0, 0, 170],
+            Color::Magenta => [255, 85, 255],
+            Color::DarkMagenta => [170, 0, 170],
+            Color::Cyan => [85, 255, 255],
+            Color::DarkCyan => [0, 170, 170],
+            Color::White => [255, 255, 255],
+            Color::Grey => [192, 192, 192],
+            Color::Rgb { r, g, b } => [r, g, b],
+            Color::AnsiValue(value) => ansi_to_rgb(value),
+        }
+    }
+
+    fn ansi_to_rgb(value: u8) -> [u8; 3] {
+        const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+        match value {
+            0..=15 => [
+                Color::Black,
+                Color::DarkRed,
+                Color::DarkGreen,
+                Color::DarkYellow,
+                Color::DarkBlue,
+                Color::DarkMagenta,
+                Color::DarkCyan,
+                Color::Grey,
+                Color::DarkGrey,
+                Color::Red,
+                Color::Green,
+                Color::Yellow,
+                Color::Blue,
+                Color::Magenta,
+                Color::Cyan,
+                Color::White,
+            ]
+            .map(to_rgb)[value as usize],
+            16..=231 => {
+                let value = value - 16;
+                [
+                    LEVELS[(value / 36) as usize],
+                    LEVELS[(value / 6 % 6) as usize],
+                    LEVELS[(value % 6) as usize],
+                ]
+            }
+            _ => {
+                let level = 8 + (value - 232) * 10;
+                [level, level, level]
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_export_asciicast() {
+        let mut recording = Recording::new((0, 0));
+        let mut cells = Buffer::new();
+        cells.draw_at(0, 0, "ab\ncd");
+        recording.frames.push(RecordedFrame {
+            at: Duration::from_millis(1500),
+            output: "ab\n\"cd\"\u{1b}[J".to_string(),
+            cells,
+        });
+        assert_eq!(
+            recording.to_asciicast(),
+            "{\"version\": 2, \"width\": 2, \"height\": 2}\n\
+             [1.500000, \"o\", \"ab\\n\\\"cd\\\"\\u001b[J\"]\n"
+        );
+    }
+
+    #[cfg(feature = "gif")]
+    #[test]
+    fn should_export_gif() {
+        let mut recording = Recording::new((0, 0));
+        let mut cells = Buffer::new();
+        cells.draw_at(0, 0, "#.");
+        recording.frames.push(RecordedFrame {
+            at: Duration::ZERO,
+            output: String::new(),
+            cells,
+        });
+        let mut bytes = vec![];
+        recording.write_gif(&mut bytes).unwrap();
+        assert!(bytes.starts_with(b"GIF89a"));
+
+        let too_wide = Recording::new((u16::MAX / 4 + 1, 1));
+        let error = too_wide.write_gif(&mut vec![]).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::mpsc::{channel, Sender};
+use std::thread::JoinHandle;
+
+use crate::canvas::backend::{Backend, TerminalBackend};
+use crate::canvas::buffer::Buffer;
+use crate::canvas::Canvas;
+
+/// A canvas running on its own thread. Frames sent to it never block the sender; when frames
+/// arrive faster than the canvas presents them, only the most recent one is drawn.
+pub struct Renderer<B: Backend + 'static = TerminalBackend> {
+    sender: Sender<Buffer>,
+    handle: JoinHandle<std::io::Result<Canvas<B>>>,
+}
+
+impl<B: Backend + 'static> Renderer<B> {
+    pub(crate) fn spawn(mut canvas: Canvas<B>) -> Self {
+        let (sender, receiver) = channel::<Buffer>();
+        let handle = std::thread::spawn(move || {
+            while let Ok(mut frame) = receiver.recv() {
+                while let Ok(newer) = receiver.try_recv() {
+                    frame = newer;
+                }
+                *canvas.buffer_mut() = frame;
+                canvas.present()?;
+            }
+            Ok(canvas)
+        });
+        Self { sender, handle }
+    }
+    pub fn sender(&self) -> Sender<Buffer> {
+        self.sender.clone()
+    }
+    /// Returns `false` if the render thread has stopped, e.g. after a terminal I/O error.
+    pub fn send(&self, frame: Buffer) -> bool {
+        self.sender.send(frame).is_ok()
+    }
+    /// Waits for the remaining frames to be drawn and hands the canvas back. Any clones of the
+    /// sender must be dropped first or this will wait forever.
+    pub fn finish(self) -> std::io::Result<Canvas<B>> {
+        drop(self.sender);
+        self.handle
+            .join()
+            .map_err(|_| std::io::Error::other("render thread panicked"))?
+    }
+}
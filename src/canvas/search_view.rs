@@ -0,0 +1,180 @@
+use std::collections::HashSet;
+
+use crate::a_star::{CustomNode, Successor};
+use crate::canvas::buffer::Buffer;
+use crate::canvas::style::{Cell, Color, Style};
+use crate::common::Numeric;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SearchStyles {
+    pub open: Cell,
+    pub closed: Cell,
+    pub current: Cell,
+    pub path: Cell,
+}
+
+impl Default for SearchStyles {
+    fn default() -> Self {
+        Self {
+            open: Cell::new('+', Style::new().fg(Color::Green)),
+            closed: Cell::new('.', Style::new().fg(Color::DarkGrey)),
+            current: Cell::new('@', Style::new().fg(Color::Yellow).bold()),
+            path: Cell::new('*', Style::new().fg(Color::Cyan).bold()),
+        }
+    }
+}
+
+/// Live view of an a* search on a 2D map. The search is observed by wrapping its successor
+/// function: the node being expanded is the current (and from then on closed) node, and the
+/// successors it returns join the open set.
+#[derive(Clone, Debug, Default)]
+pub struct SearchView {
+    background: Buffer,
+    styles: SearchStyles,
+    open: HashSet<(u16, u16)>,
+    closed: HashSet<(u16, u16)>,
+    current: Option<(u16, u16)>,
+    path: Vec<(u16, u16)>,
+    frame_every: usize,
+    expansions: usize,
+}
+
+impl SearchView {
+    pub fn new() -> Self {
+        Self {
+            frame_every: 1,
+            ..Default::default()
+        }
+    }
+    /// Drawn underneath the search, e.g. the walls of a maze.
+    pub fn with_background(mut self, background: Buffer) -> Self {
+        self.background = background;
+        self
+    }
+    pub fn with_styles(mut self, styles: SearchStyles) -> Self {
+        self.styles = styles;
+        self
+    }
+    /// Only produce a frame every `expansions` node expansions.
+    pub fn with_frame_every(mut self, expansions: usize) -> Self {
+        self.frame_every = expansions.max(1);
+        self
+    }
+    pub fn expansions(&self) -> usize {
+        self.expansions
+    }
+    pub fn expand(&mut self, node: (u16, u16), successors: impl IntoIterator<Item = (u16, u16)>) {
+        self.expansions += 1;
+        self.open.remove(&node);
+        self.closed.insert(node);
+        self.current = Some(node);
+        for successor in successors {
+            if !self.closed.contains(&successor) {
+                self.open.insert(successor);
+            }
+        }
+    }
+    pub fn show_path(&mut self, path: impl IntoIterator<Item = (u16, u16)>) {
+        self.path = path.into_iter().collect();
+        self.current = None;
+    }
+    pub fn to_buffer(&self) -> Buffer {
+        let mut buffer = self.background.clone();
+        for &(x, y) in &self.closed {
+            buffer.set_cell(x, y, self.styles.closed);
+        }
+        for &(x, y) in &self.open {
+            buffer.set_cell(x, y, self.styles.open);
+        }
+        for &(x, y) in &self.path {
+            buffer.set_cell(x, y, self.styles.path);
+        }
+        if let Some((x, y)) = self.current {
+            buffer.set_cell(x, y, self.styles.current);
+        }
+        buffer
+    }
+    /// Wraps `get_successors` for `a_star_search`, calling `on_frame` with the rendered view
+    /// every `frame_every` expansions. Sending the frame to a `Renderer` keeps terminal output
+    /// from slowing the search down.
+    pub fn wrap_successors<'a, TNode: CustomNode, TNumber: Numeric>(
+        &'a mut self,
+        position: impl Fn(&TNode) -> (u16, u16) + 'a,
+        mut get_successors: impl FnMut(&TNode) -> Vec<Successor<TNode, TNumber>> + 'a,
+        mut on_frame: impl FnMut(Buffer) + 'a,
+    ) -> impl FnMut(&TNode) -> Vec<Successor<TNode, TNumber>> + 'a {
+        move |node| {
+            let successors = get_successors(node);
+            self.expand(
+                position(node),
+                successors.iter().map(|successor| position(&successor.node)),
+            );
+            if self.expansions.is_multiple_of(self.frame_every) {
+                on_frame(self.to_buffer());
+            }
+            successors
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::a_star::{a_star_search, Node};
+
+    #[derive(Clone, Copy, Hash, Eq, PartialEq, Debug)]
+    struct Point(u16, u16);
+
+    impl Node for Point {}
+
+    #[test]
+    fn should_render_search_progress_and_path() {
+        let mut background = Buffer::new();
+        background.draw_at(0, 0, "     \n ### \n     ");
+        let walls: HashSet<(u16, u16)> = [(1, 1), (2, 1), (3, 1)].into_iter().collect();
+        let mut view = SearchView::new()
+            .with_background(background)
+            .with_styles(SearchStyles {
+                open: '+'.into(),
+                closed: '.'.into(),
+                current: '@'.into(),
+                path: '*'.into(),
+            });
+        let mut frames = vec![];
+        let result = a_star_search(
+            Point(0, 1),
+            view.wrap_successors(
+                |p: &Point| (p.0, p.1),
+                |p: &Point| {
+                    let mut successors = vec![];
+                    if p.0 > 0 {
+                        successors.push(Point(p.0 - 1, p.1));
+                    }
+                    if p.1 > 0 {
+                        successors.push(Point(p.0, p.1 - 1));
+                    }
+                    successors.push(Point(p.0 + 1, p.1));
+                    successors.push(Point(p.0, p.1 + 1));
+                    successors
+                        .into_iter()
+                        .filter(|s| s.0 < 5 && s.1 < 3 && !walls.contains(&(s.0, s.1)))
+                        .map(|s| Successor::new(s, 1))
+                        .collect()
+                },
+                |frame| frames.push(frame.to_text()),
+            ),
+            |details| details.current_node.0.abs_diff(4) as i32,
+            |p| *p == Point(4, 1),
+            None,
+        )
+        .unwrap();
+        assert_eq!(frames[0], "+\n@###\n+");
+        assert_eq!(view.expansions(), frames.len());
+
+        view.show_path(result.shortest_path.iter().map(|p| (p.0, p.1)));
+        let text = view.to_buffer().to_text();
+        assert!(text.contains('*'));
+        assert!(!text.contains('@'));
+        assert_eq!(text.lines().nth(1).unwrap(), "*###*");
+    }
+}
@@ -0,0 +1,229 @@
+use crate::canvas::buffer::Buffer;
+use crate::canvas::style::{Cell, Style};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BorderChars {
+    pub horizontal: char,
+    pub vertical: char,
+    pub top_left: char,
+    pub top_right: char,
+    pub bottom_left: char,
+    pub bottom_right: char,
+}
+
+impl BorderChars {
+    pub const LIGHT: Self = Self::new('─', '│', '┌', '┐', '└', '┘');
+    pub const HEAVY: Self = Self::new('━', '┃', '┏', '┓', '┗', '┛');
+    pub const DOUBLE: Self = Self::new('═', '║', '╔', '╗', '╚', '╝');
+    pub const ROUNDED: Self = Self::new('─', '│', '╭', '╮', '╰', '╯');
+    pub const ASCII: Self = Self::new('-', '|', '+', '+', '+', '+');
+
+    const fn new(
+        horizontal: char,
+        vertical: char,
+        top_left: char,
+        top_right: char,
+        bottom_left: char,
+        bottom_right: char,
+    ) -> Self {
+        Self {
+            horizontal,
+            vertical,
+            top_left,
+            top_right,
+            bottom_left,
+            bottom_right,
+        }
+    }
+}
+
+impl Default for BorderChars {
+    fn default() -> Self {
+        Self::LIGHT
+    }
+}
+
+pub fn line(buffer: &mut Buffer, from: (u16, u16), to: (u16, u16), cell: impl Into<Cell>) {
+    let cell = cell.into();
+    let from = (from.0 as i64, from.1 as i64);
+    let to = (to.0 as i64, to.1 as i64);
+    for (x, y) in line_points(from, to) {
+        buffer.set_cell(x as u16, y as u16, cell);
+    }
+}
+
+/// Outlines the rectangle with box-drawing characters. Rectangles narrower or shorter than two
+/// cells have no room for a border and are skipped; edges past the last column or row are drawn
+/// there instead.
+pub fn rect(
+    buffer: &mut Buffer,
+    (x, y): (u16, u16),
+    (width, height): (u16, u16),
+    border: &BorderChars,
+    style: Style,
+) {
+    if width < 2 || height < 2 {
+        return;
+    }
+    let (right, bottom) = (x.saturating_add(width - 1), y.saturating_add(height - 1));
+    for column in x.saturating_add(1)..right {
+        buffer.set_cell(column, y, Cell::new(border.horizontal, style));
+        buffer.set_cell(column, bottom, Cell::new(border.horizontal, style));
+    }
+    for row in y.saturating_add(1)..bottom {
+        buffer.set_cell(x, row, Cell::new(border.vertical, style));
+        buffer.set_cell(right, row, Cell::new(border.vertical, style));
+    }
+    buffer.set_cell(x, y, Cell::new(border.top_left, style));
+    buffer.set_cell(right, y, Cell::new(border.top_right, style));
+    buffer.set_cell(x, bottom, Cell::new(border.bottom_left, style));
+    buffer.set_cell(right, bottom, Cell::new(border.bottom_right, style));
+}
+
+pub fn fill_rect(
+    buffer: &mut Buffer,
+    (x, y): (u16, u16),
+    (width, height): (u16, u16),
+    cell: impl Into<Cell>,
+) {
+    let cell = cell.into();
+    for row in y..y.saturating_add(height) {
+        for column in x..x.saturating_add(width) {
+            buffer.set_cell(column, row, cell);
+        }
+    }
+}
+
+pub fn circle(buffer: &mut Buffer, center: (u16, u16), radius: u16, cell: impl Into<Cell>) {
+    let cell = cell.into();
+    for (x, y) in circle_points(center, radius) {
+        set_clipped(buffer, x, y, cell);
+    }
+}
+
+pub fn fill_circle(buffer: &mut Buffer, center: (u16, u16), radius: u16, cell: impl Into<Cell>) {
+    let cell = cell.into();
+    let (cx, cy, r) = (center.0 as i64, center.1 as i64, radius as i64);
+    for y in cy - r..=cy + r {
+        for x in cx - r..=cx + r {
+            if (x - cx).pow(2) + (y - cy).pow(2) <= r * r + r {
+                set_clipped(buffer, x, y, cell);
+            }
+        }
+    }
+}
+
+fn set_clipped(buffer: &mut Buffer, x: i64, y: i64, cell: Cell) {
+    if (0..=u16::MAX as i64).contains(&x) && (0..=u16::MAX as i64).contains(&y) {
+        buffer.set_cell(x as u16, y as u16, cell);
+    }
+}
+
+/// Bresenham's line between two points, both ends included.
+pub(crate) fn line_points(from: (i64, i64), to: (i64, i64)) -> Vec<(i64, i64)> {
+    let (mut x, mut y) = from;
+    let dx = (to.0 - x).abs();
+    let dy = -(to.1 - y).abs();
+    let step_x = if x < to.0 { 1 } else { -1 };
+    let step_y = if y < to.1 { 1 } else { -1 };
+    let mut error = dx + dy;
+    let mut points = Vec::with_capacity((dx - dy) as usize + 1);
+    loop {
+        points.push((x, y));
+        if (x, y) == to {
+            return points;
+        }
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            x += step_x;
+        }
+        if doubled <= dx {
+            error += dx;
+            y += step_y;
+        }
+    }
+}
+
+/// Midpoint circle algorithm.
+fn circle_points(center: (u16, u16), radius: u16) -> Vec<(i64, i64)> {
+    let (cx, cy) = (center.0 as i64, center.1 as i64);
+    let (mut x, mut y) = (radius as i64, 0i64);
+    let mut error = 1 - x;
+    let mut points = vec![];
+    while x >= y {
+        for (dx, dy) in [
+            (x, y),
+            (y, x),
+            (-y, x),
+            (-x, y),
+            (-x, -y),
+            (-y, -x),
+            (y, -x),
+            (x, -y),
+        ] {
+            points.push((cx + dx, cy + dy));
+        }
+        y += 1;
+        if error < 0 {
+            error += 2 * y + 1;
+        } else {
+            x -= 1;
+            error += 2 * (y - x) + 1;
+        }
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_draw_lines() {
+        assert_eq!(
+            line_points((0, 0), (3, 1)),
+            vec![(0, 0), (1, 0), (2, 1), (3, 1)]
+        );
+        let mut buffer = Buffer::new();
+        line(&mut buffer, (0, 0), (3, 3), '\\');
+        assert_eq!(buffer.to_text(), "\\\n \\\n  \\\n   \\");
+    }
+
+    #[test]
+    fn should_draw_rectangles() {
+        let mut buffer = Buffer::new();
+        fill_rect(&mut buffer, (1, 1), (2, 1), '#');
+        rect(
+            &mut buffer,
+            (0, 0),
+            (4, 3),
+            &BorderChars::LIGHT,
+            Style::default(),
+        );
+        assert_eq!(buffer.to_text(), "┌──┐\n│##│\n└──┘");
+
+        let mut buffer = Buffer::new();
+        rect(
+            &mut buffer,
+            (u16::MAX - 2, 0),
+            (10, 2),
+            &BorderChars::ASCII,
+            Style::default(),
+        );
+        assert_eq!(buffer.rows()[1].len(), u16::MAX as usize + 1);
+        let text = buffer.to_text();
+        assert!(text.lines().all(|line| line.trim_start() == "+-+"));
+    }
+
+    #[test]
+    fn should_draw_circles() {
+        let mut buffer = Buffer::new();
+        circle(&mut buffer, (2, 2), 2, 'o');
+        assert_eq!(buffer.to_text(), " ooo\no   o\no   o\no   o\n ooo");
+
+        let mut buffer = Buffer::new();
+        fill_circle(&mut buffer, (1, 1), 1, '#');
+        assert_eq!(buffer.to_text(), "###\n###\n###");
+    }
+}
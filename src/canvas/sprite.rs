@@ -0,0 +1,81 @@
+use crate::canvas::buffer::{char_width, Buffer, CONTINUATION};
+use crate::canvas::style::{Cell, Style};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SpriteId(pub(crate) usize);
+
+/// A block of cells drawn on top of the canvas buffer. `None` cells are transparent, so whatever
+/// is underneath shows through and reappears when the sprite moves away.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sprite {
+    pub x: u16,
+    pub y: u16,
+    cells: Vec<Vec<Option<Cell>>>,
+}
+
+impl Sprite {
+    /// Builds a sprite from multi-line text; spaces are transparent.
+    pub fn new(x: u16, y: u16, text: &str) -> Self {
+        Self::styled(x, y, text, Style::default())
+    }
+    pub fn styled(x: u16, y: u16, text: &str, style: Style) -> Self {
+        let cells = text
+            .lines()
+            .map(|line| {
+                line.chars()
+                    .flat_map(|ch| match char_width(ch) {
+                        0 => vec![],
+                        1 => vec![(ch != ' ').then(|| Cell::new(ch, style))],
+                        _ => vec![
+                            Some(Cell::new(ch, style)),
+                            Some(Cell::new(CONTINUATION, style)),
+                        ],
+                    })
+                    .collect()
+            })
+            .collect();
+        Self::from_cells(x, y, cells)
+    }
+    pub fn from_cells(x: u16, y: u16, cells: Vec<Vec<Option<Cell>>>) -> Self {
+        Self { x, y, cells }
+    }
+    pub fn cells(&self) -> &[Vec<Option<Cell>>] {
+        &self.cells
+    }
+    pub fn move_to(&mut self, x: u16, y: u16) {
+        self.x = x;
+        self.y = y;
+    }
+    pub(crate) fn draw_onto(&self, buffer: &mut Buffer) {
+        for (dy, row) in self.cells.iter().enumerate() {
+            for (dx, cell) in row.iter().enumerate() {
+                if let Some(cell) = cell {
+                    buffer.set_cell(
+                        self.x.saturating_add(dx as u16),
+                        self.y.saturating_add(dy as u16),
+                        *cell,
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_draw_with_transparency() {
+        let mut buffer = Buffer::new();
+        buffer.draw_at(0, 0, "....\n....");
+        let mut sprite = Sprite::new(1, 0, "@ @\n @");
+        sprite.draw_onto(&mut buffer);
+        assert_eq!(buffer.to_text(), ".@.@\n..@.");
+
+        buffer.draw_at(0, 0, "....\n....");
+        sprite.move_to(0, 1);
+        sprite.draw_onto(&mut buffer);
+        assert_eq!(buffer.to_text(), "....\n@.@.\n @");
+    }
+}
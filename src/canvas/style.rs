@@ -0,0 +1,107 @@
+use std::fmt::Display;
+
+pub use crossterm::style::Color;
+use crossterm::style::{Attribute, ContentStyle, StyledContent};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub bold: bool,
+    pub dim: bool,
+}
+
+impl Style {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn fg(mut self, color: Color) -> Self {
+        self.fg = Some(color);
+        self
+    }
+    pub fn bg(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+    pub fn dim(mut self) -> Self {
+        self.dim = true;
+        self
+    }
+    pub fn is_plain(&self) -> bool {
+        *self == Self::default()
+    }
+    pub fn apply<D: Display>(&self, content: D) -> StyledContent<D> {
+        StyledContent::new(self.to_content_style(), content)
+    }
+    pub(crate) fn to_content_style(self) -> ContentStyle {
+        let mut style = ContentStyle {
+            foreground_color: self.fg,
+            background_color: self.bg,
+            ..Default::default()
+        };
+        if self.bold {
+            style.attributes.set(Attribute::Bold);
+        }
+        if self.dim {
+            style.attributes.set(Attribute::Dim);
+        }
+        style
+    }
+}
+
+impl From<Color> for Style {
+    fn from(color: Color) -> Self {
+        Self::new().fg(color)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cell {
+    pub ch: char,
+    pub style: Style,
+}
+
+impl Cell {
+    pub fn new(ch: char, style: Style) -> Self {
+        Self { ch, style }
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self::new(' ', Style::default())
+    }
+}
+
+impl From<char> for Cell {
+    fn from(ch: char) -> Self {
+        Self::new(ch, Style::default())
+    }
+}
+
+impl From<(char, Style)> for Cell {
+    fn from((ch, style): (char, Style)) -> Self {
+        Self::new(ch, style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_build_content_style() {
+        let style = Style::new().fg(Color::Red).bg(Color::Black).bold();
+        let content = style.to_content_style();
+        assert_eq!(content.foreground_color, Some(Color::Red));
+        assert_eq!(content.background_color, Some(Color::Black));
+        assert!(content.attributes.has(Attribute::Bold));
+        assert!(!content.attributes.has(Attribute::Dim));
+        assert!(Style::new().is_plain());
+        assert_eq!(Cell::from('#'), Cell::new('#', Style::new()));
+    }
+}
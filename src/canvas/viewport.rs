@@ -0,0 +1,139 @@
+use crate::canvas::buffer::Buffer;
+use crate::canvas::event::{Event, KeyCode};
+use crate::canvas::style::Cell;
+
+/// A window of `size` cells into a larger world. World coordinates are signed so the world
+/// doesn't have to start at the origin.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Viewport {
+    origin: (i64, i64),
+    size: (u16, u16),
+    bounds: Option<((i64, i64), (i64, i64))>,
+    scroll_step: i64,
+}
+
+impl Viewport {
+    pub fn new(size: (u16, u16)) -> Self {
+        Self {
+            origin: (0, 0),
+            size,
+            bounds: None,
+            scroll_step: 1,
+        }
+    }
+    /// Keeps the viewport inside the world spanning `min..=max`.
+    pub fn with_bounds(mut self, min: (i64, i64), max: (i64, i64)) -> Self {
+        self.bounds = Some((min, max));
+        self.clamp();
+        self
+    }
+    pub fn with_scroll_step(mut self, step: i64) -> Self {
+        self.scroll_step = step.max(1);
+        self
+    }
+    pub fn origin(&self) -> (i64, i64) {
+        self.origin
+    }
+    pub fn size(&self) -> (u16, u16) {
+        self.size
+    }
+    pub fn resize(&mut self, size: (u16, u16)) {
+        self.size = size;
+        self.clamp();
+    }
+    pub fn center_on(&mut self, (x, y): (i64, i64)) {
+        self.origin = (x - self.size.0 as i64 / 2, y - self.size.1 as i64 / 2);
+        self.clamp();
+    }
+    pub fn scroll(&mut self, dx: i64, dy: i64) {
+        self.origin = (self.origin.0 + dx, self.origin.1 + dy);
+        self.clamp();
+    }
+    pub fn contains(&self, (x, y): (i64, i64)) -> bool {
+        self.to_screen((x, y)).is_some()
+    }
+    pub fn to_screen(&self, (x, y): (i64, i64)) -> Option<(u16, u16)> {
+        let (dx, dy) = (x - self.origin.0, y - self.origin.1);
+        ((0..self.size.0 as i64).contains(&dx) && (0..self.size.1 as i64).contains(&dy))
+            .then_some((dx as u16, dy as u16))
+    }
+    pub fn to_world(&self, (x, y): (u16, u16)) -> (i64, i64) {
+        (self.origin.0 + x as i64, self.origin.1 + y as i64)
+    }
+    /// Scrolls on arrow keys and `hjkl` (by the scroll step) and on PageUp/PageDown (by half a
+    /// screen). Returns whether the event was used.
+    pub fn handle_event(&mut self, event: &Event) -> bool {
+        let step = self.scroll_step;
+        let page = (self.size.1 as i64 / 2).max(1);
+        let (dx, dy) = match event.key_code() {
+            Some(KeyCode::Left | KeyCode::Char('h')) => (-step, 0),
+            Some(KeyCode::Right | KeyCode::Char('l')) => (step, 0),
+            Some(KeyCode::Up | KeyCode::Char('k')) => (0, -step),
+            Some(KeyCode::Down | KeyCode::Char('j')) => (0, step),
+            Some(KeyCode::PageUp) => (0, -page),
+            Some(KeyCode::PageDown) => (0, page),
+            _ => return false,
+        };
+        self.scroll(dx, dy);
+        true
+    }
+    /// Fills the visible area of `buffer` from `cell_at(world_x, world_y)`.
+    pub fn draw<C: Into<Cell>>(
+        &self,
+        buffer: &mut Buffer,
+        mut cell_at: impl FnMut(i64, i64) -> Option<C>,
+    ) {
+        for y in 0..self.size.1 {
+            for x in 0..self.size.0 {
+                let (world_x, world_y) = self.to_world((x, y));
+                if let Some(cell) = cell_at(world_x, world_y) {
+                    buffer.set_cell(x, y, cell);
+                }
+            }
+        }
+    }
+    fn clamp(&mut self) {
+        if let Some((min, max)) = self.bounds {
+            let clamp = |origin: i64, min: i64, max: i64, size: u16| {
+                let upper = (max - size as i64 + 1).max(min);
+                origin.clamp(min, upper)
+            };
+            self.origin = (
+                clamp(self.origin.0, min.0, max.0, self.size.0),
+                clamp(self.origin.1, min.1, max.1, self.size.1),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::canvas::event::{KeyEvent, KeyModifiers};
+
+    #[test]
+    fn should_center_and_clamp() {
+        let mut viewport = Viewport::new((4, 2)).with_bounds((0, 0), (99, 99));
+        viewport.center_on((50, 50));
+        assert_eq!(viewport.origin(), (48, 49));
+        assert_eq!(viewport.to_screen((50, 50)), Some((2, 1)));
+        assert_eq!(viewport.to_screen((52, 50)), None);
+        viewport.center_on((0, 0));
+        assert_eq!(viewport.origin(), (0, 0));
+        viewport.scroll(1000, 1000);
+        assert_eq!(viewport.origin(), (96, 98));
+    }
+
+    #[test]
+    fn should_scroll_on_keys_and_draw() {
+        let mut viewport = Viewport::new((3, 1));
+        let right = Event::Key(KeyEvent::new(KeyCode::Right, KeyModifiers::NONE));
+        assert!(viewport.handle_event(&right));
+        assert!(!viewport.handle_event(&Event::Quit));
+        assert_eq!(viewport.origin(), (1, 0));
+
+        let mut buffer = Buffer::new();
+        viewport.draw(&mut buffer, |x, _| char::from_digit(x as u32, 10));
+        assert_eq!(buffer.to_text(), "123");
+    }
+}
@@ -0,0 +1,136 @@
+use crate::canvas::buffer::{truncate_to_width, Buffer};
+use crate::canvas::shapes::{self, BorderChars};
+use crate::canvas::style::{Cell, Color, Style};
+
+/// A bordered region with an optional title embedded in the top border.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Panel {
+    pub position: (u16, u16),
+    pub size: (u16, u16),
+    title: Option<String>,
+    border: BorderChars,
+    style: Style,
+}
+
+impl Panel {
+    pub fn new(position: (u16, u16), size: (u16, u16)) -> Self {
+        Self {
+            position,
+            size,
+            title: None,
+            border: BorderChars::default(),
+            style: Style::default(),
+        }
+    }
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+    pub fn with_border(mut self, border: BorderChars) -> Self {
+        self.border = border;
+        self
+    }
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+    /// Position and size of the area inside the border.
+    pub fn inner(&self) -> ((u16, u16), (u16, u16)) {
+        (
+            (
+                self.position.0.saturating_add(1),
+                self.position.1.saturating_add(1),
+            ),
+            (self.size.0.saturating_sub(2), self.size.1.saturating_sub(2)),
+        )
+    }
+    /// Draws nothing when the panel is too small for its border.
+    pub fn draw(&self, buffer: &mut Buffer) {
+        if self.size.0 < 2 || self.size.1 < 2 {
+            return;
+        }
+        shapes::rect(buffer, self.position, self.size, &self.border, self.style);
+        if let Some(title) = &self.title {
+            let (x, y) = self.position;
+            let title = truncate_to_width(&format!(" {} ", title), self.size.0.saturating_sub(4));
+            buffer.draw_styled_at(x.saturating_add(2), y, &title, self.style);
+        }
+    }
+    /// Writes `text` inside the border, cutting off whatever doesn't fit.
+    pub fn draw_text(&self, buffer: &mut Buffer, text: &str, style: Style) {
+        let ((x, y), (width, height)) = self.inner();
+        if width == 0 {
+            return;
+        }
+        for (dy, line) in (0..height).zip(text.lines()) {
+            let line = truncate_to_width(line, width);
+            buffer.draw_styled_at(x, y.saturating_add(dy), &line, style);
+        }
+    }
+}
+
+/// A single line of text spanning the full width of the bottom row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StatusBar {
+    pub text: String,
+    pub style: Style,
+}
+
+impl StatusBar {
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            style: Style::new().fg(Color::Black).bg(Color::Grey),
+        }
+    }
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+    pub fn draw(&self, buffer: &mut Buffer, row: u16, width: u16) {
+        for x in 0..width {
+            buffer.set_cell(x, row, Cell::new(' ', self.style));
+        }
+        buffer.draw_styled_at(0, row, &truncate_to_width(&self.text, width), self.style);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_draw_panel_with_title() {
+        let mut buffer = Buffer::new();
+        let panel = Panel::new((0, 0), (10, 4)).with_title("a*");
+        panel.draw(&mut buffer);
+        panel.draw_text(&mut buffer, "step=12345\nopen=7\ncut", Style::default());
+        assert_eq!(
+            buffer.to_text(),
+            "┌─ a* ───┐\n│step=123│\n│open=7  │\n└────────┘"
+        );
+        assert_eq!(panel.inner(), ((1, 1), (8, 2)));
+    }
+
+    #[test]
+    fn should_skip_panels_without_room() {
+        let mut buffer = Buffer::new();
+        let panel = Panel::new((3, 3), (1, 0)).with_title("a*");
+        panel.draw(&mut buffer);
+        panel.draw_text(&mut buffer, "hidden", Style::default());
+        assert_eq!(buffer.to_text(), "");
+
+        let panel = Panel::new((u16::MAX, 0), (4, 3)).with_title("a*");
+        panel.draw(&mut buffer);
+        panel.draw_text(&mut buffer, "x", Style::default());
+        assert_eq!(panel.inner(), ((u16::MAX, 1), (2, 1)));
+    }
+
+    #[test]
+    fn should_draw_status_bar() {
+        let mut buffer = Buffer::new();
+        StatusBar::new("iteration 5").draw(&mut buffer, 1, 6);
+        assert_eq!(buffer.to_text(), "\niterat");
+        assert_eq!(buffer.rows()[1].len(), 6);
+    }
+}
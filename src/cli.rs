@@ -0,0 +1,178 @@
+use std::io::Read;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use log::LevelFilter;
+use thiserror::Error;
+
+use crate::a_star::Options;
+use crate::timer::DurationHistogram;
+
+/// How many times `RunArgs::run` repeats the solver with `--bench`.
+pub const BENCH_RUNS: usize = 10;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CliError {
+    #[error("{0} needs a value")]
+    MissingValue(String),
+    #[error("{0} doesn't take a value")]
+    UnexpectedValue(String),
+    #[error("Invalid time limit {0:?}, expected seconds")]
+    InvalidTimeLimit(String),
+}
+
+/// The flags shared by solver binaries: `--input FILE`, `--bench`, `--quiet` and
+/// `--time-limit SECS`. Anything else is left in `rest`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RunArgs {
+    pub input: Option<PathBuf>,
+    pub bench: bool,
+    pub quiet: bool,
+    pub time_limit: Option<Duration>,
+    pub rest: Vec<String>,
+}
+
+impl RunArgs {
+    pub fn from_env() -> Result<Self, CliError> {
+        Self::parse(std::env::args().skip(1))
+    }
+    /// Values can follow the flag or be attached with `=`, as in `--time-limit=2.5`.
+    pub fn parse<I: IntoIterator<Item = S>, S: Into<String>>(args: I) -> Result<Self, CliError> {
+        let mut result = Self::default();
+        let mut args = args.into_iter().map(Into::into);
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg.clone(), None),
+            };
+            let mut value = |flag: &str| {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| CliError::MissingValue(flag.to_string()))
+            };
+            match flag.as_str() {
+                "--bench" | "--quiet" if inline.is_some() => {
+                    return Err(CliError::UnexpectedValue(flag.clone()));
+                }
+                "--input" => result.input = Some(PathBuf::from(value(&flag)?)),
+                "--bench" => result.bench = true,
+                "--quiet" => result.quiet = true,
+                "--time-limit" => {
+                    let value = value(&flag)?;
+                    let seconds = value
+                        .parse::<f64>()
+                        .ok()
+                        .and_then(|s| Duration::try_from_secs_f64(s).ok())
+                        .ok_or(CliError::InvalidTimeLimit(value))?;
+                    result.time_limit = Some(seconds);
+                }
+                _ => result.rest.push(arg),
+            }
+        }
+        Ok(result)
+    }
+    /// The file given with `--input`, or stdin.
+    pub fn read_input(&self) -> std::io::Result<String> {
+        match &self.input {
+            Some(path) => std::fs::read_to_string(path),
+            None => {
+                let mut input = String::new();
+                std::io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+    /// `Warn` with `--quiet`, `Info` otherwise. Pass it to `logging::init`.
+    pub fn log_level(&self) -> LevelFilter {
+        if self.quiet {
+            LevelFilter::Warn
+        } else {
+            LevelFilter::Info
+        }
+    }
+    /// Applies `--quiet` and `--time-limit` to a_star options.
+    pub fn a_star_options(&self) -> Options {
+        let mut options = Options::default();
+        if self.quiet {
+            options = options.with_no_logs();
+        }
+        if let Some(limit) = self.time_limit {
+            options = options.with_time_limit(limit);
+        }
+        options
+    }
+    /// Runs `solve` once, or `BENCH_RUNS` times with `--bench`, logging the timings. Returns the
+    /// last result.
+    pub fn run<R>(&self, mut solve: impl FnMut() -> R) -> R {
+        if !self.bench {
+            return solve();
+        }
+        let mut histogram = DurationHistogram::new();
+        let mut result = histogram.time(&mut solve);
+        for _ in 1..BENCH_RUNS {
+            result = histogram.time(&mut solve);
+        }
+        log::info!("bench: {}", histogram);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_flags() {
+        let args = RunArgs::parse([
+            "--input",
+            "day1.txt",
+            "--quiet",
+            "--time-limit=1.5",
+            "part2",
+        ])
+        .unwrap();
+        assert_eq!(args.input, Some(PathBuf::from("day1.txt")));
+        assert!(args.quiet);
+        assert!(!args.bench);
+        assert_eq!(args.time_limit, Some(Duration::from_millis(1500)));
+        assert_eq!(args.rest, vec!["part2".to_string()]);
+        assert_eq!(args.log_level(), LevelFilter::Warn);
+    }
+
+    #[test]
+    fn should_reject_bad_values() {
+        assert_eq!(
+            RunArgs::parse(["--input"]),
+            Err(CliError::MissingValue("--input".to_string()))
+        );
+        assert_eq!(
+            RunArgs::parse(["--time-limit", "-1"]),
+            Err(CliError::InvalidTimeLimit("-1".to_string()))
+        );
+        assert_eq!(
+            RunArgs::parse(["--quiet=false"]),
+            Err(CliError::UnexpectedValue("--quiet".to_string()))
+        );
+        assert_eq!(
+            RunArgs::parse(["--bench=x"]),
+            Err(CliError::UnexpectedValue("--bench".to_string()))
+        );
+    }
+
+    #[test]
+    fn should_repeat_when_benchmarking() {
+        let mut calls = 0;
+        let args = RunArgs::parse(["--bench"]).unwrap();
+        assert_eq!(
+            args.run(|| {
+                calls += 1;
+                calls
+            }),
+            BENCH_RUNS
+        );
+        assert_eq!(RunArgs::default().run(|| 7), 7);
+    }
+}
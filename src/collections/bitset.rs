@@ -0,0 +1,167 @@
+use std::ops::{BitAnd, BitOr, BitXor};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A set of `usize` stored as one bit each. Grows to fit the largest value inserted.
+#[derive(Clone, Debug, Default)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Room for values below `bits` without reallocating.
+    pub fn with_capacity(bits: usize) -> Self {
+        Self {
+            words: Vec::with_capacity(bits.div_ceil(WORD_BITS)),
+        }
+    }
+    /// Returns whether `value` wasn't already in the set.
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (word, mask) = (value / WORD_BITS, 1 << (value % WORD_BITS));
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        let added = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        added
+    }
+    /// Returns whether `value` was in the set.
+    pub fn remove(&mut self, value: usize) -> bool {
+        let (word, mask) = (value / WORD_BITS, 1 << (value % WORD_BITS));
+        match self.words.get_mut(word) {
+            Some(bits) if *bits & mask != 0 => {
+                *bits &= !mask;
+                true
+            }
+            _ => false,
+        }
+    }
+    pub fn contains(&self, value: usize) -> bool {
+        self.words
+            .get(value / WORD_BITS)
+            .is_some_and(|bits| bits & (1 << (value % WORD_BITS)) != 0)
+    }
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|bits| bits.count_ones() as usize)
+            .sum()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&bits| bits == 0)
+    }
+    pub fn clear(&mut self) {
+        self.words.clear();
+    }
+    /// Set values in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut bits = word;
+            std::iter::from_fn(move || {
+                if bits == 0 {
+                    return None;
+                }
+                let bit = bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+                Some(i * WORD_BITS + bit)
+            })
+        })
+    }
+    pub fn union_with(&mut self, other: &BitSet) {
+        self.combine(other, |a, b| a | b);
+    }
+    pub fn intersect_with(&mut self, other: &BitSet) {
+        self.combine(other, |a, b| a & b);
+    }
+    pub fn symmetric_difference_with(&mut self, other: &BitSet) {
+        self.combine(other, |a, b| a ^ b);
+    }
+    fn combine(&mut self, other: &BitSet, f: impl Fn(u64, u64) -> u64) {
+        if other.words.len() > self.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+        for (i, bits) in self.words.iter_mut().enumerate() {
+            *bits = f(*bits, other.words.get(i).copied().unwrap_or(0));
+        }
+    }
+}
+
+/// Equal when the same values are set, however much capacity either has.
+impl PartialEq for BitSet {
+    fn eq(&self, other: &Self) -> bool {
+        let len = self.words.len().max(other.words.len());
+        (0..len).all(|i| {
+            self.words.get(i).copied().unwrap_or(0) == other.words.get(i).copied().unwrap_or(0)
+        })
+    }
+}
+
+impl Eq for BitSet {}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut result = Self::new();
+        result.extend(iter);
+        result
+    }
+}
+
+impl Extend<usize> for BitSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+macro_rules! impl_bit_op {
+    ($trait:ident, $method:ident, $with:ident) => {
+        impl $trait for &BitSet {
+            type Output = BitSet;
+            fn $method(self, other: &BitSet) -> BitSet {
+                let mut result = self.clone();
+                result.$with(other);
+                result
+            }
+        }
+    };
+}
+
+impl_bit_op!(BitAnd, bitand, intersect_with);
+impl_bit_op!(BitOr, bitor, union_with);
+impl_bit_op!(BitXor, bitxor, symmetric_difference_with);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_set_and_test_bits() {
+        let mut set = BitSet::with_capacity(100);
+        assert!(set.insert(3));
+        assert!(!set.insert(3));
+        assert!(set.insert(200));
+        assert!(set.contains(200));
+        assert!(!set.contains(199));
+        assert!(!set.contains(10_000));
+        assert!(set.remove(3));
+        assert!(!set.remove(3));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [200]);
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn should_combine_sets() {
+        let a: BitSet = [1, 2, 64, 130].into_iter().collect();
+        let b: BitSet = [2, 3, 64].into_iter().collect();
+        assert_eq!((&a & &b).iter().collect::<Vec<_>>(), [2, 64]);
+        assert_eq!((&a | &b).iter().collect::<Vec<_>>(), [1, 2, 3, 64, 130]);
+        assert_eq!((&a ^ &b).iter().collect::<Vec<_>>(), [1, 3, 130]);
+        assert_eq!((&a | &b).count_ones(), 5);
+        assert_eq!(&a & &b, [64, 2].into_iter().collect());
+    }
+}
@@ -0,0 +1,205 @@
+/// A double-ended priority queue: both the smallest and the largest value can be peeked in O(1)
+/// and popped in O(log n). Levels of the tree alternate between min-ordered (the root's) and
+/// max-ordered.
+#[derive(Clone, Debug)]
+pub struct MinMaxHeap<T> {
+    data: Vec<T>,
+}
+
+impl<T> Default for MinMaxHeap<T> {
+    fn default() -> Self {
+        Self { data: Vec::new() }
+    }
+}
+
+impl<T: Ord> MinMaxHeap<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            data: Vec::with_capacity(capacity),
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+    /// The values in no particular order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+    pub fn push(&mut self, value: T) {
+        self.data.push(value);
+        self.bubble_up(self.data.len() - 1);
+    }
+    pub fn peek_min(&self) -> Option<&T> {
+        self.data.first()
+    }
+    pub fn peek_max(&self) -> Option<&T> {
+        self.max_index().map(|i| &self.data[i])
+    }
+    pub fn pop_min(&mut self) -> Option<T> {
+        self.remove_at(0)
+    }
+    pub fn pop_max(&mut self) -> Option<T> {
+        self.remove_at(self.max_index()?)
+    }
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+    fn max_index(&self) -> Option<usize> {
+        match self.data.len() {
+            0 => None,
+            1 => Some(0),
+            2 => Some(1),
+            _ => Some(if self.data[1] >= self.data[2] { 1 } else { 2 }),
+        }
+    }
+    fn remove_at(&mut self, i: usize) -> Option<T> {
+        if i >= self.data.len() {
+            return None;
+        }
+        let value = self.data.swap_remove(i);
+        if i < self.data.len() {
+            self.trickle_down(i);
+        }
+        Some(value)
+    }
+    fn bubble_up(&mut self, i: usize) {
+        if i == 0 {
+            return;
+        }
+        let parent = (i - 1) / 2;
+        if is_min_level(i) {
+            if self.data[i] > self.data[parent] {
+                self.data.swap(i, parent);
+                self.bubble_up_by(parent, |a, b| a > b);
+            } else {
+                self.bubble_up_by(i, |a, b| a < b);
+            }
+        } else if self.data[i] < self.data[parent] {
+            self.data.swap(i, parent);
+            self.bubble_up_by(parent, |a, b| a < b);
+        } else {
+            self.bubble_up_by(i, |a, b| a > b);
+        }
+    }
+    /// Moves `i` up through its grandparents while it comes `before` them.
+    fn bubble_up_by(&mut self, mut i: usize, before: fn(&T, &T) -> bool) {
+        while i >= 3 {
+            let grandparent = (i - 3) / 4;
+            if !before(&self.data[i], &self.data[grandparent]) {
+                break;
+            }
+            self.data.swap(i, grandparent);
+            i = grandparent;
+        }
+    }
+    fn trickle_down(&mut self, i: usize) {
+        if is_min_level(i) {
+            self.trickle_down_by(i, |a, b| a < b);
+        } else {
+            self.trickle_down_by(i, |a, b| a > b);
+        }
+    }
+    /// Moves `i` down to the first of its children and grandchildren while one comes `before` it.
+    fn trickle_down_by(&mut self, mut i: usize, before: fn(&T, &T) -> bool) {
+        loop {
+            let first_child = 2 * i + 1;
+            let first_grandchild = 4 * i + 3;
+            let candidates = (first_child..first_child + 2)
+                .chain(first_grandchild..first_grandchild + 4)
+                .filter(|&j| j < self.data.len());
+            let Some(best) = candidates.reduce(|best, j| {
+                if before(&self.data[j], &self.data[best]) {
+                    j
+                } else {
+                    best
+                }
+            }) else {
+                return;
+            };
+            if !before(&self.data[best], &self.data[i]) {
+                return;
+            }
+            self.data.swap(i, best);
+            if best < first_grandchild {
+                return;
+            }
+            let parent = (best - 1) / 2;
+            if before(&self.data[parent], &self.data[best]) {
+                self.data.swap(best, parent);
+            }
+            i = best;
+        }
+    }
+}
+
+fn is_min_level(i: usize) -> bool {
+    (i + 1).ilog2().is_multiple_of(2)
+}
+
+impl<T: Ord> FromIterator<T> for MinMaxHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = Self::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<T: Ord> Extend<T> for MinMaxHeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_pop_from_both_ends() {
+        let values: Vec<u64> = (0..200u64).map(|i| i * 7919 % 101).collect();
+        let mut heap: MinMaxHeap<u64> = values.iter().copied().collect();
+        let mut sorted = values.clone();
+        sorted.sort();
+        assert_eq!(heap.len(), 200);
+        assert_eq!(heap.peek_min(), sorted.first());
+        assert_eq!(heap.peek_max(), sorted.last());
+
+        let (mut low, mut high) = (0, sorted.len());
+        for i in 0..sorted.len() {
+            if i.is_multiple_of(3) {
+                high -= 1;
+                assert_eq!(heap.pop_max(), Some(sorted[high]));
+            } else {
+                assert_eq!(heap.pop_min(), Some(sorted[low]));
+                low += 1;
+            }
+        }
+        assert!(heap.is_empty());
+        assert_eq!(heap.pop_max(), None);
+    }
+
+    #[test]
+    fn should_keep_a_bounded_beam() {
+        let mut beam = MinMaxHeap::with_capacity(4);
+        for score in [5, 1, 9, 3, 7, 2, 8] {
+            beam.push(score);
+            if beam.len() > 3 {
+                beam.pop_max();
+            }
+        }
+        let mut kept = beam.into_vec();
+        kept.sort();
+        assert_eq!(kept, [1, 2, 3]);
+    }
+}
@@ -0,0 +1,7 @@
+mod bitset;
+mod min_max_heap;
+mod range_map;
+
+pub use bitset::BitSet;
+pub use min_max_heap::MinMaxHeap;
+pub use range_map::RangeMap;
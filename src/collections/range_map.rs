@@ -0,0 +1,146 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use crate::common::Numeric;
+
+/// Maps disjoint half-open ranges to values. Inserting over existing ranges overwrites the
+/// overlap, and touching ranges with equal values are merged.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RangeMap<K, V> {
+    /// start -> (end, value)
+    ranges: BTreeMap<K, (K, V)>,
+}
+
+impl<K: Numeric, V> Default for RangeMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Numeric, V> RangeMap<K, V> {
+    pub fn new() -> Self {
+        Self {
+            ranges: BTreeMap::new(),
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+    pub fn get(&self, point: K) -> Option<&V> {
+        self.get_range(point).map(|(_, value)| value)
+    }
+    /// The range containing `point` and its value.
+    pub fn get_range(&self, point: K) -> Option<(Range<K>, &V)> {
+        let (&start, (end, value)) = self.ranges.range(..=point).next_back()?;
+        (point < *end).then_some((start..*end, value))
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (Range<K>, &V)> {
+        self.ranges
+            .iter()
+            .map(|(&start, (end, value))| (start..*end, value))
+    }
+    /// The stored ranges that overlap `range`, clipped to it.
+    pub fn overlapping(&self, range: Range<K>) -> impl Iterator<Item = (Range<K>, &V)> {
+        self.overlapping_entries(&range)
+            .map(move |(&start, (end, value))| {
+                (start.max(range.start)..(*end).min(range.end), value)
+            })
+    }
+    fn overlapping_entries(&self, range: &Range<K>) -> impl Iterator<Item = (&K, &(K, V))> {
+        let before = self
+            .ranges
+            .range(..range.start)
+            .next_back()
+            .filter(|(_, (end, _))| *end > range.start);
+        let inside = self.ranges.range(range.start..range.end.max(range.start));
+        before.into_iter().chain(inside)
+    }
+}
+
+impl<K: Numeric, V: Clone + PartialEq> RangeMap<K, V> {
+    pub fn insert(&mut self, range: Range<K>, value: V) {
+        if range.start >= range.end {
+            return;
+        }
+        self.remove(range.clone());
+        let (mut start, mut end) = (range.start, range.end);
+        if let Some((&left, (left_end, left_value))) = self.ranges.range(..start).next_back() {
+            if *left_end == start && *left_value == value {
+                start = left;
+                self.ranges.remove(&left);
+            }
+        }
+        if let Some((right_end, right_value)) = self.ranges.get(&end) {
+            if *right_value == value {
+                let right_end = *right_end;
+                self.ranges.remove(&end);
+                end = right_end;
+            }
+        }
+        self.ranges.insert(start, (end, value));
+    }
+    /// Clears `range`, splitting the ranges that stick out of it.
+    pub fn remove(&mut self, range: Range<K>) {
+        if range.start >= range.end {
+            return;
+        }
+        let starts: Vec<K> = self
+            .overlapping_entries(&range)
+            .map(|(&start, _)| start)
+            .collect();
+        for start in starts {
+            let Some((end, value)) = self.ranges.remove(&start) else {
+                continue;
+            };
+            if start < range.start {
+                self.ranges.insert(start, (range.start, value.clone()));
+            }
+            if end > range.end {
+                self.ranges.insert(range.end, (end, value));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_split_and_merge_ranges() {
+        let mut map = RangeMap::new();
+        map.insert(0..10, 'a');
+        map.insert(20..30, 'b');
+        map.insert(5..25, 'c');
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            [(0..5, &'a'), (5..25, &'c'), (25..30, &'b')]
+        );
+        assert_eq!(map.get(4), Some(&'a'));
+        assert_eq!(map.get(25), Some(&'b'));
+        assert_eq!(map.get(30), None);
+
+        map.insert(25..40, 'c');
+        assert_eq!(map.get_range(30), Some((5..40, &'c')));
+
+        map.remove(2..8);
+        assert_eq!(
+            map.overlapping(0..10).collect::<Vec<_>>(),
+            [(0..2, &'a'), (8..10, &'c')]
+        );
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn should_map_huge_sparse_ranges() {
+        let mut map = RangeMap::new();
+        map.insert(1_000_000_000i64..5_000_000_000, 1);
+        map.insert(3_000_000_000..3_000_000_001, 2);
+        assert_eq!(map.get(2_999_999_999), Some(&1));
+        assert_eq!(map.get(3_000_000_000), Some(&2));
+        assert_eq!(map.len(), 3);
+    }
+}
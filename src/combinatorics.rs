@@ -0,0 +1,114 @@
+/// Every way of picking one item from each of `sequences`, varying the last one fastest.
+pub fn cartesian_product<T: Clone, S: AsRef<[T]>>(sequences: &[S]) -> CartesianProduct<'_, T, S> {
+    let done = sequences
+        .iter()
+        .any(|sequence| sequence.as_ref().is_empty());
+    CartesianProduct {
+        sequences,
+        indices: vec![0; sequences.len()],
+        done,
+        _item: std::marker::PhantomData,
+    }
+}
+
+pub struct CartesianProduct<'a, T, S> {
+    sequences: &'a [S],
+    indices: Vec<usize>,
+    done: bool,
+    _item: std::marker::PhantomData<T>,
+}
+
+impl<T: Clone, S: AsRef<[T]>> Iterator for CartesianProduct<'_, T, S> {
+    type Item = Vec<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = self
+            .indices
+            .iter()
+            .zip(self.sequences)
+            .map(|(&i, sequence)| sequence.as_ref()[i].clone())
+            .collect();
+        // odometer: bump the last index, carrying into the ones before
+        self.done = true;
+        for (i, sequence) in self.indices.iter_mut().zip(self.sequences).rev() {
+            *i += 1;
+            if *i < sequence.as_ref().len() {
+                self.done = false;
+                break;
+            }
+            *i = 0;
+        }
+        Some(result)
+    }
+}
+
+/// Every subset of `items`, keeping their order, from the empty set up to all of them.
+pub fn power_set<T: Clone>(items: &[T]) -> PowerSet<'_, T> {
+    assert!(
+        items.len() < 64,
+        "power set of {} items is too large",
+        items.len()
+    );
+    PowerSet {
+        items,
+        mask: 0,
+        end: 1 << items.len(),
+    }
+}
+
+pub struct PowerSet<'a, T> {
+    items: &'a [T],
+    mask: u64,
+    end: u64,
+}
+
+impl<T: Clone> Iterator for PowerSet<'_, T> {
+    type Item = Vec<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.mask == self.end {
+            return None;
+        }
+        let subset = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.mask & (1 << i) != 0)
+            .map(|(_, item)| item.clone())
+            .collect();
+        self.mask += 1;
+        Some(subset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_enumerate_products() {
+        let grid = [vec![1, 2], vec![10, 20, 30]];
+        let products: Vec<_> = cartesian_product(&grid).collect();
+        assert_eq!(products.len(), 6);
+        assert_eq!(products[0], [1, 10]);
+        assert_eq!(products[1], [1, 20]);
+        assert_eq!(products[5], [2, 30]);
+
+        let empty: [&[i32]; 2] = [&[1], &[]];
+        assert_eq!(cartesian_product(&empty).count(), 0);
+        assert_eq!(
+            cartesian_product::<i32, Vec<i32>>(&[]).collect::<Vec<_>>(),
+            [Vec::<i32>::new()]
+        );
+    }
+
+    #[test]
+    fn should_enumerate_subsets() {
+        let subsets: Vec<_> = power_set(&['a', 'b', 'c']).collect();
+        assert_eq!(subsets.len(), 8);
+        assert_eq!(subsets[0], Vec::<char>::new());
+        assert_eq!(subsets[3], ['a', 'b']);
+        assert_eq!(subsets[7], ['a', 'b', 'c']);
+    }
+}
@@ -126,3 +126,62 @@ impl NumericWithUnitValue for isize {
         1
     }
 }
+
+pub trait AsF64: Copy {
+    fn as_f64(self) -> f64;
+}
+
+impl AsF64 for i32 {
+    fn as_f64(self) -> f64 {
+        self as f64
+    }
+}
+impl AsF64 for i64 {
+    fn as_f64(self) -> f64 {
+        self as f64
+    }
+}
+impl AsF64 for usize {
+    fn as_f64(self) -> f64 {
+        self as f64
+    }
+}
+impl AsF64 for isize {
+    fn as_f64(self) -> f64 {
+        self as f64
+    }
+}
+impl AsF64 for u32 {
+    fn as_f64(self) -> f64 {
+        self as f64
+    }
+}
+impl AsF64 for u64 {
+    fn as_f64(self) -> f64 {
+        self as f64
+    }
+}
+impl AsF64 for f32 {
+    fn as_f64(self) -> f64 {
+        self as f64
+    }
+}
+impl AsF64 for f64 {
+    fn as_f64(self) -> f64 {
+        self
+    }
+}
+
+/// `(year, month, day)` of the day `days` after 1970-01-01, after Howard Hinnant's
+/// `civil_from_days`.
+pub(crate) const fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let (era, doe) = (z.div_euclid(146_097), z.rem_euclid(146_097));
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    (year, month as u32, day as u32)
+}
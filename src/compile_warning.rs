@@ -1,5 +1,16 @@
+use crate::common::civil_from_days;
+
+/// `compile_warning!(name, "message")` always warns. With a trailing `cfg(...)` predicate, e.g.
+/// `compile_warning!(name, "untested on windows", cfg(windows))`, it only warns when the
+/// predicate holds.
 #[macro_export]
 macro_rules! compile_warning {
+    (
+    $name:ident, $message:expr, cfg($($predicate:tt)*) $(,)*
+) => {
+        #[cfg($($predicate)*)]
+        $crate::compile_warning!($name, $message);
+    };
     (
     $name:ident, $message:expr $(,)*
 ) => {
@@ -13,3 +24,136 @@ macro_rules! compile_warning {
         }
     };
 }
+
+/// Like `compile_warning!` until `deadline` (`"YYYY-MM-DD"`, UTC), then a compile error. The
+/// date is checked whenever the calling crate is compiled.
+#[cfg(feature = "derive")]
+#[macro_export]
+macro_rules! compile_warning_until {
+    (
+    $name:ident, $message:expr, $deadline:expr $(,)*
+) => {
+        $crate::compile_warning!($name, $message);
+        const _: () = ::core::assert!(
+            $crate::compile_warning::is_before($crate::compile_warning::__build_day!(), $deadline),
+            ::core::concat!("deadline ", $deadline, " has passed: ", $message)
+        );
+    };
+}
+
+/// Like `compile_warning!` until the calling crate's version reaches `version`, then a compile
+/// error.
+#[macro_export]
+macro_rules! todo_by {
+    (
+    $name:ident, $version:expr, $message:expr $(,)*
+) => {
+        $crate::compile_warning!($name, $message);
+        const _: () = ::core::assert!(
+            !$crate::compile_warning::version_reached(::core::env!("CARGO_PKG_VERSION"), $version),
+            ::core::concat!("version ", $version, " is reached: ", $message)
+        );
+    };
+}
+
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub use utils_derive::build_day as __build_day;
+
+/// Whether the day `day` after 1970-01-01 comes before `deadline`, a `YYYY-MM-DD` date.
+#[doc(hidden)]
+pub const fn is_before(day: i64, deadline: &str) -> bool {
+    let deadline = deadline.as_bytes();
+    assert!(
+        deadline.len() == 10 && deadline[4] == b'-' && deadline[7] == b'-',
+        "deadline must be YYYY-MM-DD"
+    );
+    let (year, month, day) = civil_from_days(day);
+    let (deadline_year, deadline_month, deadline_day) = (
+        date_part(deadline, 0, 4),
+        date_part(deadline, 5, 7),
+        date_part(deadline, 8, 10),
+    );
+    if year != deadline_year {
+        year < deadline_year
+    } else if month as i64 != deadline_month {
+        (month as i64) < deadline_month
+    } else {
+        (day as i64) < deadline_day
+    }
+}
+
+const fn date_part(date: &[u8], start: usize, end: usize) -> i64 {
+    let mut i = start;
+    let mut value = 0;
+    while i < end {
+        assert!(date[i].is_ascii_digit(), "deadline must be YYYY-MM-DD");
+        value = value * 10 + (date[i] - b'0') as i64;
+        i += 1;
+    }
+    value
+}
+
+/// Whether `version` is at least `threshold`, comparing the numeric `major.minor.patch` parts.
+/// Missing parts count as 0, and pre-release and build suffixes are ignored.
+#[doc(hidden)]
+pub const fn version_reached(version: &str, threshold: &str) -> bool {
+    let (version, threshold) = (version.as_bytes(), threshold.as_bytes());
+    let (mut i, mut j) = (0, 0);
+    while i < version.len() || j < threshold.len() {
+        let (current, next_i) = version_part(version, i);
+        let (target, next_j) = version_part(threshold, j);
+        if current != target {
+            return current > target;
+        }
+        (i, j) = (next_i, next_j);
+    }
+    true
+}
+
+/// The number starting at `start` and where the next one starts, or `(0, len)` once past the
+/// numeric parts.
+const fn version_part(version: &[u8], start: usize) -> (u64, usize) {
+    let mut i = start;
+    let mut value = 0;
+    while i < version.len() && version[i].is_ascii_digit() {
+        value = value * 10 + (version[i] - b'0') as u64;
+        i += 1;
+    }
+    if i < version.len() && version[i] == b'.' {
+        (value, i + 1)
+    } else {
+        (value, version.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_convert_days_to_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+        assert_eq!(civil_from_days(20_453), (2025, 12, 31));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    }
+
+    #[test]
+    fn should_compare_dates() {
+        // 2025-06-30, 2025-12-31 and 2026-01-01
+        assert!(is_before(20_269, "2025-12-31"));
+        assert!(!is_before(20_453, "2025-12-31"));
+        assert!(!is_before(20_454, "2025-12-31"));
+        assert!(is_before(20_453, "2026-01-01"));
+    }
+
+    #[test]
+    fn should_compare_versions() {
+        assert!(!version_reached("0.8.3", "0.9"));
+        assert!(version_reached("0.9.0", "0.9"));
+        assert!(version_reached("0.10.0", "0.9"));
+        assert!(version_reached("1.0.0-beta.1", "1.0.0"));
+        assert!(!version_reached("0.2.0", "0.2.1"));
+    }
+}
@@ -0,0 +1,48 @@
+/// Splits input into the 64-byte blocks MD5 and SHA-1 compress, and pads the last one.
+#[derive(Clone, Debug)]
+pub(super) struct Blocks {
+    buffer: [u8; 64],
+    filled: usize,
+    /// Total bytes seen.
+    length: u64,
+}
+
+impl Default for Blocks {
+    fn default() -> Self {
+        Self {
+            buffer: [0; 64],
+            filled: 0,
+            length: 0,
+        }
+    }
+}
+
+impl Blocks {
+    pub(super) fn update(&mut self, mut data: &[u8], mut compress: impl FnMut(&[u8; 64])) {
+        self.length += data.len() as u64;
+        while !data.is_empty() {
+            let take = (64 - self.filled).min(data.len());
+            self.buffer[self.filled..self.filled + take].copy_from_slice(&data[..take]);
+            self.filled += take;
+            data = &data[take..];
+            if self.filled == 64 {
+                compress(&self.buffer);
+                self.filled = 0;
+            }
+        }
+    }
+    /// Appends the padding and the bit length, encoded by `encode_length`.
+    pub(super) fn finish(
+        mut self,
+        encode_length: fn(u64) -> [u8; 8],
+        mut compress: impl FnMut(&[u8; 64]),
+    ) {
+        let bits = encode_length(self.length.wrapping_mul(8));
+        let padding = if self.filled < 56 { 56 } else { 120 } - self.filled;
+        let mut tail = [0; 64];
+        tail[0] = 0x80;
+        self.update(&tail[..padding], &mut compress);
+        self.update(&bits, &mut compress);
+        debug_assert_eq!(self.filled, 0);
+    }
+}
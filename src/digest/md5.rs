@@ -0,0 +1,116 @@
+use crate::digest::blocks::Blocks;
+use crate::encoding::hex_encode;
+
+const SHIFTS: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9,
+    14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10, 15,
+    21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+/// `floor(abs(sin(i + 1)) * 2^32)`.
+const CONSTANTS: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+    0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed, 0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+    0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+    0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+    0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+/// Incremental MD5. Broken for security purposes; fine for puzzles and checksums.
+#[derive(Clone, Debug)]
+pub struct Md5 {
+    state: [u32; 4],
+    blocks: Blocks,
+}
+
+impl Default for Md5 {
+    fn default() -> Self {
+        Self {
+            state: [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476],
+            blocks: Blocks::default(),
+        }
+    }
+}
+
+impl Md5 {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        let state = &mut self.state;
+        self.blocks
+            .update(data.as_ref(), |block| compress(state, block));
+    }
+    pub fn finish(self) -> [u8; 16] {
+        let mut state = self.state;
+        self.blocks
+            .finish(u64::to_le_bytes, |block| compress(&mut state, block));
+        let mut digest = [0; 16];
+        for (chunk, word) in digest.chunks_exact_mut(4).zip(state) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        digest
+    }
+    pub fn finish_hex(self) -> String {
+        hex_encode(self.finish())
+    }
+}
+
+fn compress(state: &mut [u32; 4], block: &[u8; 64]) {
+    let words: [u32; 16] =
+        std::array::from_fn(|i| u32::from_le_bytes(block[i * 4..i * 4 + 4].try_into().unwrap()));
+    let [mut a, mut b, mut c, mut d] = *state;
+    for i in 0..64 {
+        let (f, g) = match i / 16 {
+            0 => ((b & c) | (!b & d), i),
+            1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+            2 => (b ^ c ^ d, (3 * i + 5) % 16),
+            _ => (c ^ (b | !d), (7 * i) % 16),
+        };
+        let f = f
+            .wrapping_add(a)
+            .wrapping_add(CONSTANTS[i])
+            .wrapping_add(words[g]);
+        a = d;
+        d = c;
+        c = b;
+        b = b.wrapping_add(f.rotate_left(SHIFTS[i]));
+    }
+    for (word, value) in state.iter_mut().zip([a, b, c, d]) {
+        *word = word.wrapping_add(value);
+    }
+}
+
+pub fn md5(data: impl AsRef<[u8]>) -> [u8; 16] {
+    let mut md5 = Md5::new();
+    md5.update(data);
+    md5.finish()
+}
+
+pub fn md5_hex(data: impl AsRef<[u8]>) -> String {
+    hex_encode(md5(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_compute_md5() {
+        assert_eq!(md5_hex(""), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(md5_hex("abc"), "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(md5_hex("abcdef609043"), "000001dbbfa3a5c83a2d506429c7b00e");
+        assert_eq!(
+            md5_hex("a".repeat(1000)),
+            "cabe45dcc9ae5b66ba86600cca6b8ba8"
+        );
+
+        let mut md5 = Md5::new();
+        md5.update("The quick brown fox ");
+        md5.update("jumps over the lazy dog");
+        assert_eq!(md5.finish_hex(), "9e107d9d372bb6826bd81d3542a419d6");
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(any(feature = "md5", feature = "sha1"))]
+mod blocks;
+#[cfg(feature = "md5")]
+mod md5;
+#[cfg(feature = "sha1")]
+mod sha1;
+
+#[cfg(feature = "md5")]
+pub use md5::{md5, md5_hex, Md5};
+#[cfg(feature = "sha1")]
+pub use sha1::{sha1, sha1_hex, Sha1};
+
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+/// The reflected IEEE polynomial, as used by zip, gzip and png.
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// Incremental CRC-32 (IEEE).
+#[derive(Clone, Copy, Debug)]
+pub struct Crc32 {
+    crc: u32,
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self { crc: !0 }
+    }
+}
+
+impl Crc32 {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        for &byte in data.as_ref() {
+            self.crc = CRC32_TABLE[((self.crc ^ byte as u32) & 0xff) as usize] ^ (self.crc >> 8);
+        }
+    }
+    pub fn finish(&self) -> u32 {
+        !self.crc
+    }
+    pub fn finish_hex(&self) -> String {
+        format!("{:08x}", self.finish())
+    }
+}
+
+pub fn crc32(data: impl AsRef<[u8]>) -> u32 {
+    let mut crc = Crc32::new();
+    crc.update(data);
+    crc.finish()
+}
+
+pub fn crc32_hex(data: impl AsRef<[u8]>) -> String {
+    format!("{:08x}", crc32(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_compute_crc32() {
+        assert_eq!(crc32(""), 0);
+        assert_eq!(crc32_hex("abc"), "352441c2");
+        assert_eq!(
+            crc32("The quick brown fox jumps over the lazy dog"),
+            0x414f_a339
+        );
+
+        let mut crc = Crc32::new();
+        crc.update("The quick brown fox ");
+        crc.update(b"jumps over the lazy dog");
+        assert_eq!(crc.finish_hex(), "414fa339");
+    }
+}
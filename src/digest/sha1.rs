@@ -0,0 +1,108 @@
+use crate::digest::blocks::Blocks;
+use crate::encoding::hex_encode;
+
+/// Incremental SHA-1. Broken for security purposes; fine for puzzles and checksums.
+#[derive(Clone, Debug)]
+pub struct Sha1 {
+    state: [u32; 5],
+    blocks: Blocks,
+}
+
+impl Default for Sha1 {
+    fn default() -> Self {
+        Self {
+            state: [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0],
+            blocks: Blocks::default(),
+        }
+    }
+}
+
+impl Sha1 {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        let state = &mut self.state;
+        self.blocks
+            .update(data.as_ref(), |block| compress(state, block));
+    }
+    pub fn finish(self) -> [u8; 20] {
+        let mut state = self.state;
+        self.blocks
+            .finish(u64::to_be_bytes, |block| compress(&mut state, block));
+        let mut digest = [0; 20];
+        for (chunk, word) in digest.chunks_exact_mut(4).zip(state) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+    pub fn finish_hex(self) -> String {
+        hex_encode(self.finish())
+    }
+}
+
+fn compress(state: &mut [u32; 5], block: &[u8; 64]) {
+    let mut words = [0u32; 80];
+    for (i, chunk) in block.chunks_exact(4).enumerate() {
+        words[i] = u32::from_be_bytes(chunk.try_into().unwrap());
+    }
+    for i in 16..80 {
+        words[i] = (words[i - 3] ^ words[i - 8] ^ words[i - 14] ^ words[i - 16]).rotate_left(1);
+    }
+    let [mut a, mut b, mut c, mut d, mut e] = *state;
+    for (i, word) in words.into_iter().enumerate() {
+        let (f, k) = match i / 20 {
+            0 => ((b & c) | (!b & d), 0x5a827999),
+            1 => (b ^ c ^ d, 0x6ed9eba1),
+            2 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+            _ => (b ^ c ^ d, 0xca62c1d6),
+        };
+        let next = a
+            .rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(word);
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = next;
+    }
+    for (word, value) in state.iter_mut().zip([a, b, c, d, e]) {
+        *word = word.wrapping_add(value);
+    }
+}
+
+pub fn sha1(data: impl AsRef<[u8]>) -> [u8; 20] {
+    let mut sha1 = Sha1::new();
+    sha1.update(data);
+    sha1.finish()
+}
+
+pub fn sha1_hex(data: impl AsRef<[u8]>) -> String {
+    hex_encode(sha1(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_compute_sha1() {
+        assert_eq!(sha1_hex(""), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+        assert_eq!(sha1_hex("abc"), "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_eq!(
+            sha1_hex("a".repeat(1000)),
+            "291e9a6c66994949b57ba5e650361e98fc36b1ba"
+        );
+
+        let mut sha1 = Sha1::new();
+        sha1.update("The quick brown fox ");
+        sha1.update("jumps over the lazy dog");
+        assert_eq!(
+            sha1.finish_hex(),
+            "2fd4e1c67a2d28fced849ee1bb76e7391b93eb12"
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use thiserror::Error;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DecodeError {
+    #[error("Invalid character {ch:?} at {position}")]
+    InvalidCharacter { ch: char, position: usize },
+    #[error("Invalid length {0}")]
+    InvalidLength(usize),
+}
+
+/// Lowercase hex, two digits per byte.
+pub fn hex_encode(bytes: impl AsRef<[u8]>) -> String {
+    let bytes = bytes.as_ref();
+    let mut result = String::with_capacity(bytes.len() * 2);
+    for &byte in bytes {
+        result.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+        result.push(HEX_DIGITS[(byte & 0xf) as usize] as char);
+    }
+    result
+}
+
+/// Accepts either case.
+pub fn hex_decode(text: &str) -> Result<Vec<u8>, DecodeError> {
+    if !text.len().is_multiple_of(2) {
+        return Err(DecodeError::InvalidLength(text.len()));
+    }
+    let digit = |position: usize| {
+        let ch = text.as_bytes()[position] as char;
+        ch.to_digit(16)
+            .map(|value| value as u8)
+            .ok_or_else(|| invalid_character(text, position))
+    };
+    (0..text.len())
+        .step_by(2)
+        .map(|i| Ok((digit(i)? << 4) | digit(i + 1)?))
+        .collect()
+}
+
+/// Standard alphabet, padded with `=`.
+pub fn base64_encode(bytes: impl AsRef<[u8]>) -> String {
+    let bytes = bytes.as_ref();
+    let mut result = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, &byte)| {
+            group | ((byte as u32) << (16 - 8 * i))
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (group >> (18 - 6 * i)) & 0x3f;
+                result.push(BASE64_ALPHABET[index as usize] as char);
+            } else {
+                result.push('=');
+            }
+        }
+    }
+    result
+}
+
+/// Standard alphabet. The `=` padding is optional.
+pub fn base64_decode(text: &str) -> Result<Vec<u8>, DecodeError> {
+    let data = text.trim_end_matches('=');
+    let padding = text.len() - data.len();
+    if padding > 2 || data.len() % 4 == 1 || (padding > 0 && !text.len().is_multiple_of(4)) {
+        return Err(DecodeError::InvalidLength(text.len()));
+    }
+    let mut result = Vec::with_capacity(data.len() * 3 / 4);
+    let (mut bits, mut count) = (0u32, 0);
+    for (position, byte) in data.bytes().enumerate() {
+        let value = BASE64_ALPHABET
+            .iter()
+            .position(|&b| b == byte)
+            .ok_or_else(|| invalid_character(text, position))?;
+        bits = (bits << 6) | value as u32;
+        count += 6;
+        if count >= 8 {
+            count -= 8;
+            result.push((bits >> count) as u8);
+        }
+    }
+    Ok(result)
+}
+
+/// The character starting at byte `position`, which may be in the middle of a multibyte one.
+fn invalid_character(text: &str, position: usize) -> DecodeError {
+    let start = (0..=position)
+        .rev()
+        .find(|&i| text.is_char_boundary(i))
+        .unwrap_or(0);
+    DecodeError::InvalidCharacter {
+        ch: text[start..].chars().next().unwrap_or_default(),
+        position: start,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_round_trip_hex() {
+        assert_eq!(hex_encode([0x00, 0x7f, 0xab, 0xff]), "007fabff");
+        assert_eq!(hex_decode("007FabfF"), Ok(vec![0x00, 0x7f, 0xab, 0xff]));
+        assert_eq!(hex_decode(""), Ok(vec![]));
+        assert_eq!(hex_decode("abc"), Err(DecodeError::InvalidLength(3)));
+        assert_eq!(
+            hex_decode("0g"),
+            Err(DecodeError::InvalidCharacter {
+                ch: 'g',
+                position: 1
+            })
+        );
+        assert_eq!(
+            hex_decode("0é0"),
+            Err(DecodeError::InvalidCharacter {
+                ch: 'é',
+                position: 1
+            })
+        );
+    }
+
+    #[test]
+    fn should_round_trip_base64() {
+        for (plain, encoded) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(base64_encode(plain), encoded);
+            assert_eq!(base64_decode(encoded), Ok(plain.as_bytes().to_vec()));
+        }
+        assert_eq!(base64_decode("Zm8"), Ok(b"fo".to_vec()));
+        assert_eq!(base64_encode([0xfb, 0xff]), "+/8=");
+        assert_eq!(base64_decode("Zm9vY"), Err(DecodeError::InvalidLength(5)));
+        assert_eq!(base64_decode("Zg="), Err(DecodeError::InvalidLength(3)));
+        assert_eq!(
+            base64_decode("Zm-v"),
+            Err(DecodeError::InvalidCharacter {
+                ch: '-',
+                position: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use std::hash::{BuildHasherDefault, Hash, Hasher};
+
+pub trait GetHash: Hash {
+    /// `DefaultHasher`, which may change between Rust releases.
+    fn get_hash(&self) -> u64 {
+        self.get_hash_with::<std::collections::hash_map::DefaultHasher>()
+    }
+    fn get_hash_with<H: Hasher + Default>(&self) -> u64 {
+        let mut hasher = H::default();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+    /// Same on every run and platform, for ids that are persisted. See `StableHasher`.
+    fn get_stable_hash(&self) -> u64 {
+        self.get_hash_with::<StableHasher>()
+    }
+}
+
+impl<T: Hash + ?Sized> GetHash for T {}
+
+/// Mixes `hash` into `seed`, for combining the hashes of several parts into one id.
+pub fn hash_combine(seed: u64, hash: u64) -> u64 {
+    seed ^ hash
+        .wrapping_add(0x9e37_79b9_7f4a_7c15)
+        .wrapping_add(seed << 6)
+        .wrapping_add(seed >> 2)
+}
+
+/// The hasher rustc uses: very fast on small keys such as integers and coordinates, but not
+/// resistant to crafted collisions.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FxHasher {
+    hash: u64,
+}
+
+const FX_SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+impl FxHasher {
+    fn add(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(FX_SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+        for &byte in chunks.remainder() {
+            self.add(byte as u64);
+        }
+    }
+    fn write_u8(&mut self, i: u8) {
+        self.add(i as u64);
+    }
+    fn write_u16(&mut self, i: u16) {
+        self.add(i as u64);
+    }
+    fn write_u32(&mut self, i: u32) {
+        self.add(i as u64);
+    }
+    fn write_u64(&mut self, i: u64) {
+        self.add(i);
+    }
+    fn write_usize(&mut self, i: usize) {
+        self.add(i as u64);
+    }
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+/// For `HashMap::with_hasher` and `HashSet::with_hasher`.
+pub type FxBuildHasher = BuildHasherDefault<FxHasher>;
+
+/// 64-bit FNV-1a over little-endian bytes, so a value hashes the same on every run and
+/// platform as long as its `Hash` impl doesn't change. `usize` is always hashed as a `u64` and
+/// `isize` as an `i64`, so 32- and 64-bit platforms agree.
+#[derive(Clone, Copy, Debug)]
+pub struct StableHasher {
+    hash: u64,
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self {
+            hash: 0xcbf2_9ce4_8422_2325,
+        }
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.hash = (self.hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+    fn write_isize(&mut self, i: isize) {
+        self.write_i64(i as i64);
+    }
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn should_hash_stably() {
+        let mut hasher = StableHasher::default();
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(
+            (3u32, 4u32).get_stable_hash(),
+            (3u32, 4u32).get_stable_hash()
+        );
+        assert_ne!(
+            (3u32, 4u32).get_stable_hash(),
+            (4u32, 3u32).get_stable_hash()
+        );
+        assert_eq!([1usize, 2].get_stable_hash(), [1u64, 2].get_stable_hash());
+        assert_eq!((-1isize).get_stable_hash(), (-1i64).get_stable_hash());
+        assert_ne!(hash_combine(1, 2), hash_combine(2, 1));
+    }
+
+    #[test]
+    fn should_work_as_map_hasher() {
+        let mut map: HashMap<(i32, i32), u8, FxBuildHasher> = HashMap::default();
+        map.insert((1, 2), 3);
+        assert_eq!(map.get(&(1, 2)), Some(&3));
+        assert_eq!(
+            (1, 2).get_hash_with::<FxHasher>(),
+            (1, 2).get_hash_with::<FxHasher>()
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::VecDeque;
+use std::iter::Peekable;
+
+pub trait IteratorExt: Iterator + Sized {
+    /// Overlapping windows of `n` consecutive items, like `slice::windows` for any iterator.
+    fn windows_iter(self, n: usize) -> Windows<Self>
+    where
+        Self::Item: Clone,
+    {
+        assert!(n > 0, "window size must be positive");
+        Windows {
+            iter: self,
+            window: VecDeque::with_capacity(n),
+            n,
+        }
+    }
+    /// Runs of consecutive items where `same_chunk(previous, next)` holds for each neighboring
+    /// pair, like `slice::chunk_by`.
+    fn chunk_by<F: FnMut(&Self::Item, &Self::Item) -> bool>(
+        self,
+        same_chunk: F,
+    ) -> ChunkBy<Self, F> {
+        ChunkBy {
+            iter: self.peekable(),
+            same_chunk,
+        }
+    }
+    /// Each item with the one after it: `[a, b, c]` gives `(a, b), (b, c)`.
+    fn pairwise(self) -> Pairwise<Self>
+    where
+        Self::Item: Clone,
+    {
+        Pairwise {
+            iter: self,
+            previous: None,
+        }
+    }
+}
+
+impl<I: Iterator> IteratorExt for I {}
+
+pub struct Windows<I: Iterator> {
+    iter: I,
+    window: VecDeque<I::Item>,
+    n: usize,
+}
+
+impl<I: Iterator> Iterator for Windows<I>
+where
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.window.len() == self.n {
+            self.window.pop_front();
+        }
+        while self.window.len() < self.n {
+            self.window.push_back(self.iter.next()?);
+        }
+        Some(self.window.iter().cloned().collect())
+    }
+}
+
+pub struct ChunkBy<I: Iterator, F> {
+    iter: Peekable<I>,
+    same_chunk: F,
+}
+
+impl<I: Iterator, F: FnMut(&I::Item, &I::Item) -> bool> Iterator for ChunkBy<I, F> {
+    type Item = Vec<I::Item>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chunk = vec![self.iter.next()?];
+        while let Some(next) = self
+            .iter
+            .next_if(|next| (self.same_chunk)(chunk.last().unwrap(), next))
+        {
+            chunk.push(next);
+        }
+        Some(chunk)
+    }
+}
+
+pub struct Pairwise<I: Iterator> {
+    iter: I,
+    previous: Option<I::Item>,
+}
+
+impl<I: Iterator> Iterator for Pairwise<I>
+where
+    I::Item: Clone,
+{
+    type Item = (I::Item, I::Item);
+    fn next(&mut self) -> Option<Self::Item> {
+        let previous = match self.previous.take() {
+            Some(previous) => previous,
+            None => self.iter.next()?,
+        };
+        let next = self.iter.next()?;
+        self.previous = Some(next.clone());
+        Some((previous, next))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_adapt_iterators() {
+        let windows: Vec<_> = "abcd".chars().windows_iter(3).collect();
+        assert_eq!(windows, [vec!['a', 'b', 'c'], vec!['b', 'c', 'd']]);
+        assert_eq!("ab".chars().windows_iter(3).count(), 0);
+
+        let runs: Vec<_> = [1, 1, 2, 3, 3, 3, 1]
+            .into_iter()
+            .chunk_by(|a, b| a == b)
+            .collect();
+        assert_eq!(runs, [vec![1, 1], vec![2], vec![3, 3, 3], vec![1]]);
+
+        let steps: Vec<_> = [1, 4, 9]
+            .into_iter()
+            .pairwise()
+            .map(|(a, b)| b - a)
+            .collect();
+        assert_eq!(steps, [3, 5]);
+        assert_eq!([1].into_iter().pairwise().count(), 0);
+    }
+}
@@ -1,9 +1,31 @@
+extern crate self as utils;
+
 pub mod a_star;
 pub mod bisection_method;
+pub mod bits;
+pub mod cache;
+pub mod cli;
+pub mod collections;
+pub mod combinatorics;
 pub mod compile_warning;
+pub mod digest;
+pub mod encoding;
+pub mod hashing;
+pub mod iterators;
+pub mod logging;
+pub mod math;
 pub mod num_cpus;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod priority_queue;
+pub mod sequence;
+pub mod simulation;
+pub mod state_machine;
+pub mod store;
+pub mod strings;
 pub mod timeout;
 pub mod timer;
+#[cfg(feature = "canvas")]
 pub mod canvas;
 pub mod pretty_print;
 pub mod temp;
@@ -0,0 +1,183 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use log::{LevelFilter, Log, Metadata, Record};
+use thiserror::Error;
+
+use crate::common::civil_from_days;
+use crate::temp::TempFile;
+
+#[derive(Debug, Error)]
+pub enum InitError {
+    #[error("Failed to open the log file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    AlreadyInitialized(#[from] log::SetLoggerError),
+}
+
+/// Logs to stderr as `<UTC time> <LEVEL> <target>] <message>`. `RUST_LOG` overrides
+/// `default_level`, either as a level or as `target=level` directives separated by commas.
+pub fn init(default_level: LevelFilter) -> Result<(), InitError> {
+    install(Logger::from_env(default_level, None))
+}
+
+/// Like `init`, also appending every line to `path`.
+pub fn init_with_file(default_level: LevelFilter, path: impl AsRef<Path>) -> Result<(), InitError> {
+    let file = File::options().create(true).append(true).open(path)?;
+    install(Logger::from_env(default_level, Some(file)))
+}
+
+/// Like `init_with_file`, with a new `.log` file under `temp::root()` that is kept after exit.
+pub fn init_with_temp_file(default_level: LevelFilter) -> Result<PathBuf, InitError> {
+    let path = TempFile::builder()
+        .with_suffix(".log")
+        .create_file()?
+        .keep();
+    init_with_file(default_level, &path)?;
+    Ok(path)
+}
+
+fn install(logger: Logger) -> Result<(), InitError> {
+    let max_level = logger.max_level();
+    log::set_boxed_logger(Box::new(logger))?;
+    log::set_max_level(max_level);
+    Ok(())
+}
+
+struct Logger {
+    default_level: LevelFilter,
+    /// `(target, level)`, longest target first so the most specific one matches.
+    directives: Vec<(String, LevelFilter)>,
+    file: Option<Mutex<File>>,
+}
+
+impl Logger {
+    fn from_env(default_level: LevelFilter, file: Option<File>) -> Self {
+        let mut logger = Self::parse(
+            &std::env::var("RUST_LOG").unwrap_or_default(),
+            default_level,
+        );
+        logger.file = file.map(Mutex::new);
+        logger
+    }
+    fn parse(spec: &str, mut default_level: LevelFilter) -> Self {
+        let mut directives = vec![];
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((target, level)) => {
+                    if let Ok(level) = level.parse() {
+                        directives.push((target.to_string(), level));
+                    }
+                }
+                None => match directive.parse() {
+                    Ok(level) => default_level = level,
+                    Err(_) => directives.push((directive.to_string(), LevelFilter::Trace)),
+                },
+            }
+        }
+        directives.sort_by_key(|(target, _)| std::cmp::Reverse(target.len()));
+        Self {
+            default_level,
+            directives,
+            file: None,
+        }
+    }
+    fn level_for(&self, target: &str) -> LevelFilter {
+        self.directives
+            .iter()
+            .find(|(prefix, _)| {
+                target == prefix
+                    || target
+                        .strip_prefix(prefix.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+            .map_or(self.default_level, |(_, level)| *level)
+    }
+    fn max_level(&self) -> LevelFilter {
+        self.directives
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.default_level, Ord::max)
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level_for(metadata.target())
+    }
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line = format!(
+            "{} {:<5} {}] {}",
+            timestamp(SystemTime::now()),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        eprintln!("{}", line);
+        if let Some(file) = &self.file {
+            let _ = writeln!(file.lock().unwrap_or_else(|e| e.into_inner()), "{}", line);
+        }
+    }
+    fn flush(&self) {
+        if let Some(file) = &self.file {
+            let _ = file.lock().unwrap_or_else(|e| e.into_inner()).flush();
+        }
+    }
+}
+
+/// `YYYY-MM-DDTHH:MM:SS.mmmZ`
+fn timestamp(time: SystemTime) -> String {
+    let elapsed = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = elapsed.as_secs();
+    let (days, seconds_of_day) = ((seconds / 86_400) as i64, seconds % 86_400);
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day / 60 % 60,
+        seconds_of_day % 60,
+        elapsed.subsec_millis()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn should_parse_directives() {
+        let logger = Logger::parse(
+            "warn, utils::a_star=trace,utils=info, noisy",
+            LevelFilter::Info,
+        );
+        assert_eq!(logger.level_for("app"), LevelFilter::Warn);
+        assert_eq!(logger.level_for("utils::timer"), LevelFilter::Info);
+        assert_eq!(
+            logger.level_for("utils::a_star::parallel"),
+            LevelFilter::Trace
+        );
+        assert_eq!(logger.level_for("utils_other"), LevelFilter::Warn);
+        assert_eq!(logger.level_for("noisy"), LevelFilter::Trace);
+        assert_eq!(logger.max_level(), LevelFilter::Trace);
+        assert_eq!(
+            Logger::parse("", LevelFilter::Debug).max_level(),
+            LevelFilter::Debug
+        );
+    }
+
+    #[test]
+    fn should_format_timestamps() {
+        let time = UNIX_EPOCH + Duration::from_millis(1_767_225_599_123);
+        assert_eq!(timestamp(time), "2025-12-31T23:59:59.123Z");
+    }
+}
@@ -0,0 +1,151 @@
+use std::ops::{Bound, RangeBounds};
+
+use crate::common::Numeric;
+
+/// Cumulative sums of a sequence, answering range-sum queries in O(1).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrefixSums<T> {
+    /// `sums[i]` is the sum of the first `i` values.
+    sums: Vec<T>,
+}
+
+impl<T: Numeric> PrefixSums<T> {
+    pub fn new(values: &[T]) -> Self {
+        values.iter().copied().collect()
+    }
+    pub fn len(&self) -> usize {
+        self.sums.len() - 1
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub fn total(&self) -> T {
+        self.sums[self.len()]
+    }
+    /// The sum of the values at `range`. Panics if it's out of bounds, like slicing would.
+    pub fn sum(&self, range: impl RangeBounds<usize>) -> T {
+        let (start, end) = resolve(range, self.len());
+        self.sums[end] - self.sums[start]
+    }
+}
+
+impl<T: Numeric> FromIterator<T> for PrefixSums<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut sums = vec![T::default()];
+        let mut total = T::default();
+        for value in iter {
+            total = total + value;
+            sums.push(total);
+        }
+        Self { sums }
+    }
+}
+
+/// 2D prefix sums over a rectangular grid, answering rectangle-sum queries in O(1).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SummedAreaTable<T> {
+    width: usize,
+    height: usize,
+    /// `(width + 1) * (height + 1)` values, `sums[y][x]` covering everything above and to the
+    /// left of `(x, y)`.
+    sums: Vec<T>,
+}
+
+impl<T: Numeric> SummedAreaTable<T> {
+    /// Panics if the rows have different lengths.
+    pub fn new<R: AsRef<[T]>>(grid: &[R]) -> Self {
+        let width = grid.first().map_or(0, |row| row.as_ref().len());
+        let height = grid.len();
+        let mut sums = vec![T::default(); (width + 1) * (height + 1)];
+        for (y, row) in grid.iter().enumerate() {
+            let row = row.as_ref();
+            assert_eq!(row.len(), width, "row {} has a different length", y);
+            let mut row_total = T::default();
+            for (x, &value) in row.iter().enumerate() {
+                row_total = row_total + value;
+                sums[(y + 1) * (width + 1) + x + 1] = sums[y * (width + 1) + x + 1] + row_total;
+            }
+        }
+        Self {
+            width,
+            height,
+            sums,
+        }
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    pub fn total(&self) -> T {
+        self.sum(.., ..)
+    }
+    /// The sum of the rectangle at columns `xs` and rows `ys`. Panics if either is out of
+    /// bounds.
+    pub fn sum(&self, xs: impl RangeBounds<usize>, ys: impl RangeBounds<usize>) -> T {
+        let (x0, x1) = resolve(xs, self.width);
+        let (y0, y1) = resolve(ys, self.height);
+        let at = |x: usize, y: usize| self.sums[y * (self.width + 1) + x];
+        at(x1, y1) + at(x0, y0) - at(x0, y1) - at(x1, y0)
+    }
+}
+
+/// `range` as `start..end` within `0..len`.
+fn resolve(range: impl RangeBounds<usize>, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end + 1,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    assert!(
+        start <= end && end <= len,
+        "range {}..{} out of bounds for length {}",
+        start,
+        end,
+        len
+    );
+    (start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_sum_ranges() {
+        let sums = PrefixSums::new(&[3, -1, 4, 1, 5]);
+        assert_eq!(sums.len(), 5);
+        assert_eq!(sums.total(), 12);
+        assert_eq!(sums.sum(1..3), 3);
+        assert_eq!(sums.sum(1..=3), 4);
+        assert_eq!(sums.sum(2..), 10);
+        assert_eq!(sums.sum(..0), 0);
+        assert_eq!(sums.sum(5..), 0);
+        assert!(PrefixSums::<i32>::new(&[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn should_panic_past_the_end() {
+        PrefixSums::new(&[1, 2]).sum(1..3);
+    }
+
+    #[test]
+    fn should_sum_rectangles() {
+        let grid = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+        let table = SummedAreaTable::new(&grid);
+        assert_eq!((table.width(), table.height()), (3, 3));
+        assert_eq!(table.total(), 45);
+        assert_eq!(table.sum(1.., 1..), 28);
+        assert_eq!(table.sum(0..1, ..), 12);
+        assert_eq!(table.sum(.., 2..=2), 24);
+        assert_eq!(table.sum(1..=1, 1..=1), 5);
+        assert_eq!(table.sum(2..2, ..), 0);
+    }
+}
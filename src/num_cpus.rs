@@ -1,3 +0,0 @@
-pub fn get() -> usize {
-    num_cpus::get()
-}
@@ -0,0 +1,48 @@
+#[cfg(feature = "parallel")]
+mod thread_pool;
+
+#[cfg(feature = "parallel")]
+pub use thread_pool::{Scope, ThreadPool};
+
+/// Logical CPUs, counting each hyperthread.
+pub fn get() -> usize {
+    num_cpus::get()
+}
+
+/// Physical cores, for memory-bound work that hyperthreads only slow down.
+pub fn get_physical() -> usize {
+    num_cpus::get_physical()
+}
+
+/// Restricts the calling thread to logical CPU `cpu`. Only supported on Linux; returns whether
+/// it took effect.
+pub fn pin_current_thread(cpu: usize) -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        if cpu >= libc::CPU_SETSIZE as usize {
+            return false;
+        }
+        // SAFETY: `set` is a plain bitmask that lives for the duration of the call
+        unsafe {
+            let mut set: libc::cpu_set_t = std::mem::zeroed();
+            libc::CPU_SET(cpu, &mut set);
+            libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) == 0
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = cpu;
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_count_cpus() {
+        assert!(get_physical() >= 1);
+        assert!(!pin_current_thread(usize::MAX));
+    }
+}
@@ -0,0 +1,322 @@
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::parallel::CancellationToken;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+type ScopedJob<'a> = Box<dyn FnOnce() + Send + 'a>;
+
+/// How often a waiting `scope` checks the queue for jobs it can run itself.
+const HELP_INTERVAL: Duration = Duration::from_millis(1);
+
+thread_local! {
+    /// Identifies the pool a worker thread belongs to, or 0 outside of any pool.
+    static CURRENT_POOL: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A fixed set of worker threads. Dropping the pool finishes the queued jobs and joins the
+/// workers.
+pub struct ThreadPool {
+    size: usize,
+    sender: Option<Sender<Job>>,
+    receiver: Arc<Mutex<Receiver<Job>>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl ThreadPool {
+    pub fn new(size: usize) -> Self {
+        Self::spawn(size, false)
+    }
+    /// Pins worker `i` to logical CPU `i`, wrapping around. See `pin_current_thread`.
+    pub fn pinned(size: usize) -> Self {
+        Self::spawn(size, true)
+    }
+    fn spawn(size: usize, pin: bool) -> Self {
+        let size = size.max(1);
+        let cpus = super::get();
+        let (sender, receiver) = channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..size)
+            .map(|i| {
+                let receiver = receiver.clone();
+                std::thread::Builder::new()
+                    .name(format!("pool-{}", i))
+                    .spawn(move || {
+                        CURRENT_POOL.set(Arc::as_ptr(&receiver) as usize);
+                        if pin && !super::pin_current_thread(i % cpus) {
+                            log::warn!("Failed to pin pool-{} to CPU {}", i, i % cpus);
+                        }
+                        while let Some(job) = next(&receiver) {
+                            run(job);
+                        }
+                    })
+                    .expect("failed to spawn thread pool worker")
+            })
+            .collect();
+        Self {
+            size,
+            sender: Some(sender),
+            receiver,
+            workers,
+        }
+    }
+    /// One worker per detected CPU.
+    pub fn with_default_size() -> Self {
+        Self::new(super::get())
+    }
+    /// One worker per physical core. Never more than `with_default_size`, since some hosts report
+    /// more physical cores than the logical CPUs available to the process.
+    pub fn with_physical_size() -> Self {
+        Self::new(super::get_physical().min(super::get()))
+    }
+    pub fn size(&self) -> usize {
+        self.size
+    }
+    pub fn execute(&self, job: impl FnOnce() + Send + 'static) {
+        if let Some(sender) = &self.sender {
+            sender
+                .send(Box::new(job))
+                .expect("thread pool workers have stopped");
+        }
+    }
+    /// Skips `job` if `token` is cancelled by the time a worker picks it up.
+    pub fn execute_until_cancelled(
+        &self,
+        token: &CancellationToken,
+        job: impl FnOnce() + Send + 'static,
+    ) {
+        let token = token.clone();
+        self.execute(move || {
+            if !token.is_cancelled() {
+                job();
+            }
+        });
+    }
+    /// Runs jobs that may borrow from the caller's stack on the pool's workers, and waits for all
+    /// of them before returning. A job can open a scope on its own pool too; it runs queued jobs
+    /// while it waits. A panicking job panics the caller.
+    pub fn scope<'a, R>(&self, f: impl FnOnce(&Scope<'a>) -> R) -> R {
+        let scope = Scope {
+            sender: self
+                .sender
+                .clone()
+                .expect("thread pool workers have stopped"),
+            state: Arc::default(),
+            _lifetime: PhantomData,
+        };
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| f(&scope)));
+        self.wait_for(&scope.state);
+        match result {
+            Err(payload) => std::panic::resume_unwind(payload),
+            Ok(_) if scope.state.panicked.load(Ordering::SeqCst) => {
+                panic!("a scoped thread pool job panicked")
+            }
+            Ok(result) => result,
+        }
+    }
+    fn wait_for(&self, state: &ScopeState) {
+        let mut pending = state.lock();
+        while *pending > 0 {
+            if !self.is_current_worker() {
+                pending = state.done.wait(pending).unwrap_or_else(|e| e.into_inner());
+                continue;
+            }
+            // a worker waiting on its own pool runs queued jobs meanwhile, in case every worker
+            // is waiting like this; idle workers hold the lock while they wait for jobs
+            drop(pending);
+            let job = match self.receiver.try_lock() {
+                Ok(receiver) => receiver.try_recv().ok(),
+                Err(_) => None,
+            };
+            pending = state.lock();
+            match job {
+                Some(job) => {
+                    drop(pending);
+                    run(job);
+                    pending = state.lock();
+                }
+                None if *pending > 0 => {
+                    pending = state
+                        .done
+                        .wait_timeout(pending, HELP_INTERVAL)
+                        .unwrap_or_else(|e| e.into_inner())
+                        .0;
+                }
+                None => {}
+            }
+        }
+    }
+    fn is_current_worker(&self) -> bool {
+        CURRENT_POOL.get() == Arc::as_ptr(&self.receiver) as usize
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        self.sender.take();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn next(receiver: &Mutex<Receiver<Job>>) -> Option<Job> {
+    receiver
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .recv()
+        .ok()
+}
+
+fn run(job: Job) {
+    // keep the worker alive; the panic message is already printed
+    if std::panic::catch_unwind(AssertUnwindSafe(job)).is_err() {
+        log::error!("Thread pool job panicked");
+    }
+}
+
+/// The jobs of one `scope` call that haven't finished yet.
+#[derive(Default)]
+struct ScopeState {
+    pending: Mutex<usize>,
+    done: Condvar,
+    panicked: AtomicBool,
+}
+
+impl ScopeState {
+    fn lock(&self) -> std::sync::MutexGuard<'_, usize> {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+pub struct Scope<'a> {
+    sender: Sender<Job>,
+    state: Arc<ScopeState>,
+    /// Invariant, so a job can't borrow anything that ends before the whole `scope` call does.
+    _lifetime: PhantomData<&'a mut &'a ()>,
+}
+
+impl<'a> Scope<'a> {
+    pub fn spawn(&self, job: impl FnOnce() + Send + 'a) {
+        let job: ScopedJob<'a> = Box::new(job);
+        // SAFETY: `ThreadPool::scope` waits for every job spawned here before it returns, even
+        // when it panics, so whatever the job borrows for `'a` outlives it
+        let job = unsafe { std::mem::transmute::<ScopedJob<'a>, Job>(job) };
+        let state = self.state.clone();
+        *state.lock() += 1;
+        self.sender
+            .send(Box::new(move || {
+                if std::panic::catch_unwind(AssertUnwindSafe(job)).is_err() {
+                    state.panicked.store(true, Ordering::SeqCst);
+                }
+                let mut pending = state.lock();
+                *pending -= 1;
+                if *pending == 0 {
+                    state.done.notify_all();
+                }
+            }))
+            .expect("thread pool workers have stopped");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn should_run_scoped_jobs_borrowing_the_stack() {
+        let pool = ThreadPool::new(3);
+        let values: Vec<u64> = (1..=100).collect();
+        let mut sums = [0u64; 10];
+        pool.scope(|scope| {
+            for (chunk, sum) in values.chunks(10).zip(sums.iter_mut()) {
+                scope.spawn(move || *sum = chunk.iter().sum());
+            }
+        });
+        assert_eq!(sums.iter().sum::<u64>(), 5050);
+        assert_eq!(sums[0], 55);
+    }
+
+    #[test]
+    fn should_run_scoped_jobs_on_the_workers() {
+        let pool = ThreadPool::new(1);
+        let mut names = vec![];
+        let mut nested = 0;
+        pool.scope(|scope| {
+            scope.spawn(|| {
+                names.push(std::thread::current().name().map(str::to_string));
+                // the only worker is busy with this job, so the inner scope runs its own
+                pool.scope(|inner| inner.spawn(|| nested += 1));
+            });
+        });
+        assert_eq!(names, [Some("pool-0".to_string())]);
+        assert_eq!(nested, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "scoped thread pool job panicked")]
+    fn should_panic_the_caller_of_a_panicking_scope() {
+        let pool = ThreadPool::new(2);
+        pool.scope(|scope| scope.spawn(|| panic!("expected")));
+    }
+
+    #[test]
+    fn should_finish_jobs_on_drop() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let pool = ThreadPool::new(2);
+        for i in 0..20 {
+            let count = count.clone();
+            pool.execute(move || {
+                if i == 3 {
+                    panic!("expected");
+                }
+                std::thread::sleep(std::time::Duration::from_millis(1));
+                count.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        drop(pool);
+        assert_eq!(count.load(Ordering::SeqCst), 19);
+        assert!(ThreadPool::with_default_size().size() >= 1);
+        assert!(ThreadPool::with_physical_size().size() >= 1);
+    }
+
+    #[test]
+    fn should_skip_cancelled_jobs() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let token = CancellationToken::new();
+        let pool = ThreadPool::new(1);
+        let (started, start) = std::sync::mpsc::channel();
+        pool.execute(move || start.recv().unwrap());
+        for _ in 0..5 {
+            let count = count.clone();
+            pool.execute_until_cancelled(&token, move || {
+                count.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        token.cancel();
+        started.send(()).unwrap();
+        drop(pool);
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn should_run_jobs_on_pinned_workers() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let pool = ThreadPool::pinned(2);
+        for _ in 0..10 {
+            let count = count.clone();
+            pool.execute(move || {
+                count.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        drop(pool);
+        assert_eq!(count.load(Ordering::SeqCst), 10);
+    }
+}
@@ -0,0 +1,68 @@
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::timeout::Timeout;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Thread exceeded its time budget")]
+pub struct Elapsed;
+
+pub struct JoinHandleWithDeadline<T> {
+    result: Receiver<T>,
+    handle: JoinHandle<()>,
+    timeout: Timeout,
+}
+
+/// Runs `f` on a new thread with a time budget starting now. The thread can't be stopped, so
+/// after a timeout it keeps running detached.
+pub fn spawn_with_timeout<T: Send + 'static>(
+    duration: Duration,
+    f: impl FnOnce() -> T + Send + 'static,
+) -> JoinHandleWithDeadline<T> {
+    let (sender, result) = channel();
+    let timeout = Timeout::start(duration);
+    let handle = std::thread::spawn(move || {
+        let _ = sender.send(f());
+    });
+    JoinHandleWithDeadline {
+        result,
+        handle,
+        timeout,
+    }
+}
+
+impl<T> JoinHandleWithDeadline<T> {
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+    /// Waits for the rest of the budget. A panic in the thread is resumed here.
+    pub fn join(self) -> Result<T, Elapsed> {
+        match self.result.recv_timeout(self.timeout.remaining()) {
+            Ok(value) => Ok(value),
+            Err(RecvTimeoutError::Timeout) => Err(Elapsed),
+            Err(RecvTimeoutError::Disconnected) => match self.handle.join() {
+                Err(panic) => std::panic::resume_unwind(panic),
+                Ok(()) => unreachable!("the thread sends its result before exiting"),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_join_within_deadline() {
+        let handle = spawn_with_timeout(Duration::from_secs(5), || 42);
+        assert_eq!(handle.join(), Ok(42));
+
+        let handle = spawn_with_timeout(Duration::from_millis(20), || {
+            std::thread::sleep(Duration::from_millis(200));
+        });
+        assert_eq!(handle.join(), Err(Elapsed));
+    }
+}
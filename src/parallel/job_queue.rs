@@ -0,0 +1,148 @@
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{channel, sync_channel, Receiver, Sender, SyncSender};
+use std::sync::{Arc, Mutex};
+
+use thiserror::Error;
+
+pub type JobId = u64;
+type Job<R> = Box<dyn FnOnce() -> R + Send + 'static>;
+type JobResult<R> = (JobId, Result<R, JobPanicked>);
+
+/// The result of a job that panicked instead of returning.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[error("Job panicked: {0}")]
+pub struct JobPanicked(pub String);
+
+/// Runs submitted jobs on worker threads and streams `(id, result)` pairs back as each one
+/// finishes, exactly one per submitted job. At most `capacity` jobs wait in the queue; submitting
+/// more blocks the producer.
+pub struct JobQueue<R> {
+    sender: JobSender<R>,
+    results: Receiver<JobResult<R>>,
+}
+
+/// Submits jobs to a `JobQueue` from other threads.
+pub struct JobSender<R> {
+    jobs: SyncSender<(JobId, Job<R>)>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl<R> Clone for JobSender<R> {
+    fn clone(&self) -> Self {
+        Self {
+            jobs: self.jobs.clone(),
+            next_id: self.next_id.clone(),
+        }
+    }
+}
+
+impl<R: Send + 'static> JobSender<R> {
+    pub fn submit(&self, job: impl FnOnce() -> R + Send + 'static) -> JobId {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.jobs
+            .send((id, Box::new(job)))
+            .expect("job queue workers have stopped");
+        id
+    }
+}
+
+impl<R: Send + 'static> JobQueue<R> {
+    pub fn new(workers: usize, capacity: usize) -> Self {
+        let (jobs, queue) = sync_channel::<(JobId, Job<R>)>(capacity);
+        let (results_sender, results) = channel();
+        let queue = Arc::new(Mutex::new(queue));
+        for i in 0..workers.max(1) {
+            let queue = queue.clone();
+            let results: Sender<JobResult<R>> = results_sender.clone();
+            std::thread::Builder::new()
+                .name(format!("job-queue-{}", i))
+                .spawn(move || loop {
+                    let next = queue.lock().unwrap_or_else(|e| e.into_inner()).recv();
+                    let Ok((id, job)) = next else {
+                        break;
+                    };
+                    let result =
+                        std::panic::catch_unwind(AssertUnwindSafe(job)).map_err(|payload| {
+                            let message = match payload.downcast::<String>() {
+                                Ok(message) => *message,
+                                Err(payload) => match payload.downcast::<&str>() {
+                                    Ok(message) => message.to_string(),
+                                    Err(_) => "unknown panic payload".to_string(),
+                                },
+                            };
+                            JobPanicked(message)
+                        });
+                    // the consumer may have stopped listening
+                    let _ = results.send((id, result));
+                })
+                .expect("failed to spawn job queue worker");
+        }
+        Self {
+            sender: JobSender {
+                jobs,
+                next_id: Arc::new(AtomicU64::new(0)),
+            },
+            results,
+        }
+    }
+    /// One worker per detected CPU.
+    pub fn with_default_size(capacity: usize) -> Self {
+        Self::new(crate::num_cpus::get(), capacity)
+    }
+    /// Blocks while the queue is full.
+    pub fn submit(&self, job: impl FnOnce() -> R + Send + 'static) -> JobId {
+        self.sender.submit(job)
+    }
+    pub fn sender(&self) -> JobSender<R> {
+        self.sender.clone()
+    }
+    /// Finished jobs, in completion order.
+    pub fn results(&self) -> &Receiver<JobResult<R>> {
+        &self.results
+    }
+    /// Stops accepting jobs from this handle. The returned iterator yields the remaining results
+    /// and ends once every `JobSender` is dropped and the queue has drained.
+    pub fn close(self) -> impl Iterator<Item = JobResult<R>> {
+        let Self { sender, results } = self;
+        drop(sender);
+        results.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_stream_results() {
+        let queue = JobQueue::new(2, 1);
+        let first = queue.submit(|| 1);
+        assert_eq!(queue.results().recv().unwrap(), (first, Ok(1)));
+
+        let sender = queue.sender();
+        let producer = std::thread::spawn(move || {
+            for i in 0..10 {
+                sender.submit(move || i * 10);
+            }
+        });
+        let mut results: Vec<_> = queue.close().collect();
+        producer.join().unwrap();
+        results.sort_by_key(|(id, _)| *id);
+        assert_eq!(results.len(), 10);
+        assert_eq!(results[0], (1, Ok(0)));
+        assert_eq!(results[9], (10, Ok(90)));
+    }
+
+    #[test]
+    fn should_report_panicking_jobs() {
+        let queue = JobQueue::new(1, 4);
+        queue.submit(|| panic!("bad input {}", 3));
+        queue.submit(|| 2);
+        let results: Vec<_> = queue.close().collect();
+        assert_eq!(
+            results,
+            [(0, Err(JobPanicked("bad input 3".to_string()))), (1, Ok(2))]
+        );
+    }
+}
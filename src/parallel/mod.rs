@@ -0,0 +1,216 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+mod cancellation;
+mod deadline;
+mod job_queue;
+mod pipeline;
+
+pub use cancellation::CancellationToken;
+pub use deadline::{spawn_with_timeout, Elapsed, JoinHandleWithDeadline};
+pub use job_queue::{JobId, JobPanicked, JobQueue, JobSender};
+pub use pipeline::Pipeline;
+
+use crate::num_cpus::ThreadPool;
+
+/// The pool behind the functions that don't take one, with one worker per detected CPU. It is
+/// created on first use and lives for the rest of the program.
+pub(crate) fn default_pool() -> &'static ThreadPool {
+    static POOL: OnceLock<ThreadPool> = OnceLock::new();
+    POOL.get_or_init(ThreadPool::with_default_size)
+}
+
+/// Maps `items` across `num_cpus::get()` threads and returns the results in input order.
+pub fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    map_in(default_pool(), items, f)
+}
+
+/// Like `map`, on the workers of `pool`.
+pub fn map_in<T: Sync, R: Send>(
+    pool: &ThreadPool,
+    items: &[T],
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    // a few chunks per worker evens out uneven work
+    map_chunked_in(pool, items, items.len().div_ceil(pool.size() * 4), f)
+}
+
+/// Like `map`, with workers taking `chunk_size` items at a time. Larger chunks suit cheap `f`.
+pub fn map_chunked<T: Sync, R: Send>(
+    items: &[T],
+    chunk_size: usize,
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    map_chunked_in(default_pool(), items, chunk_size, f)
+}
+
+/// Like `map_chunked`, on the workers of `pool`.
+pub fn map_chunked_in<T: Sync, R: Send>(
+    pool: &ThreadPool,
+    items: &[T],
+    chunk_size: usize,
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    map_chunks(pool, items, chunk_size, None, f)
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Like `map_chunked`, but stops starting new items once `token` is cancelled. Items that never
+/// ran are `None`. `f` can hold a clone of the token to stop early itself.
+pub fn map_until_cancelled<T: Sync, R: Send>(
+    items: &[T],
+    chunk_size: usize,
+    token: &CancellationToken,
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<Option<R>> {
+    map_until_cancelled_in(default_pool(), items, chunk_size, token, f)
+}
+
+/// Like `map_until_cancelled`, on the workers of `pool`.
+pub fn map_until_cancelled_in<T: Sync, R: Send>(
+    pool: &ThreadPool,
+    items: &[T],
+    chunk_size: usize,
+    token: &CancellationToken,
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<Option<R>> {
+    map_chunks(pool, items, chunk_size, Some(token), f)
+}
+
+fn map_chunks<T: Sync, R: Send>(
+    pool: &ThreadPool,
+    items: &[T],
+    chunk_size: usize,
+    token: Option<&CancellationToken>,
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<Option<R>> {
+    let chunks: Vec<&[T]> = items.chunks(chunk_size.max(1)).collect();
+    let threads = pool.size().min(chunks.len()).max(1);
+    let is_cancelled = || token.is_some_and(|token| token.is_cancelled());
+    let next = AtomicUsize::new(0);
+    let done = Mutex::new(Vec::with_capacity(chunks.len()));
+    let work = || loop {
+        let i = next.fetch_add(1, Ordering::Relaxed);
+        let Some(chunk) = chunks.get(i) else {
+            break;
+        };
+        let results: Vec<Option<R>> = chunk
+            .iter()
+            .map(|item| (!is_cancelled()).then(|| f(item)))
+            .collect();
+        done.lock().unwrap().push((i, results));
+    };
+    if threads == 1 {
+        work();
+    } else {
+        pool.scope(|scope| {
+            for _ in 0..threads {
+                scope.spawn(work);
+            }
+        });
+    }
+    let mut done = done.into_inner().unwrap();
+    done.sort_unstable_by_key(|(i, _)| *i);
+    done.into_iter().flat_map(|(_, results)| results).collect()
+}
+
+/// Calls `f(offset, chunk)` for each disjoint `chunk_size` chunk of `slice` across
+/// `num_cpus::get()` threads, where `offset` is the chunk's index in `slice`. For in-place updates
+/// of large buffers, e.g. one grid row per chunk.
+pub fn for_each_chunk<T: Send>(
+    slice: &mut [T],
+    chunk_size: usize,
+    f: impl Fn(usize, &mut [T]) + Sync,
+) {
+    for_each_chunk_in(default_pool(), slice, chunk_size, f)
+}
+
+/// Like `for_each_chunk`, on the workers of `pool`.
+pub fn for_each_chunk_in<T: Send>(
+    pool: &ThreadPool,
+    slice: &mut [T],
+    chunk_size: usize,
+    f: impl Fn(usize, &mut [T]) + Sync,
+) {
+    let chunk_size = chunk_size.max(1);
+    let chunks: Vec<(usize, &mut [T])> = slice
+        .chunks_mut(chunk_size)
+        .enumerate()
+        .map(|(i, chunk)| (i * chunk_size, chunk))
+        .collect();
+    let threads = pool.size().min(chunks.len());
+    let chunks = Mutex::new(chunks.into_iter());
+    let work = || loop {
+        let Some((offset, chunk)) = chunks.lock().unwrap().next() else {
+            break;
+        };
+        f(offset, chunk);
+    };
+    pool.scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(work);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_map_in_order() {
+        let items: Vec<u64> = (0..1000).collect();
+        let expected: Vec<u64> = items.iter().map(|x| x * x).collect();
+        assert_eq!(map(&items, |x| x * x), expected);
+        assert_eq!(map_chunked(&items, 7, |x| x * x), expected);
+        assert_eq!(map_chunked(&items, 0, |x| x * x), expected);
+        assert!(map(&[] as &[u64], |x| x * 2).is_empty());
+
+        let pool = ThreadPool::new(4);
+        assert_eq!(map_in(&pool, &items, |x| x * x), expected);
+        assert_eq!(map_chunked_in(&pool, &items, 7, |x| x * x), expected);
+    }
+
+    #[test]
+    fn should_return_partial_results_when_cancelled() {
+        let items: Vec<u64> = (0..100).collect();
+        let token = CancellationToken::new();
+        let pool = ThreadPool::new(4);
+        let results = map_until_cancelled_in(&pool, &items, 1, &token, |&x| {
+            if x == 10 {
+                token.cancel();
+            }
+            x
+        });
+        assert_eq!(results.len(), 100);
+        assert_eq!(results[10], Some(10));
+        assert!(results.iter().any(|result| result.is_none()));
+        assert!(results
+            .iter()
+            .enumerate()
+            .all(|(i, result)| result.is_none_or(|x| x == i as u64)));
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let results = map_until_cancelled(&items, 10, &token, |&x| x);
+        assert!(results.iter().all(|result| result.is_none()));
+    }
+
+    #[test]
+    fn should_update_chunks_in_place() {
+        let pool = ThreadPool::new(3);
+        let mut grid = vec![0usize; 6 * 4];
+        for_each_chunk_in(&pool, &mut grid, 6, |offset, row| {
+            for (x, cell) in row.iter_mut().enumerate() {
+                *cell = offset + x;
+            }
+        });
+        assert_eq!(grid, (0..24).collect::<Vec<_>>());
+
+        let mut uneven = vec![1; 10];
+        for_each_chunk(&mut uneven, 4, |offset, chunk| chunk.fill(offset));
+        assert_eq!(uneven, [0, 0, 0, 0, 4, 4, 4, 4, 8, 8]);
+    }
+}
@@ -0,0 +1,137 @@
+use std::sync::mpsc::{sync_channel, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+const DEFAULT_CAPACITY: usize = 64;
+
+/// Stages connected by bounded channels, each running on its own thread(s):
+/// `Pipeline::source(lines).map(parse).map_parallel(4, simulate).sink(aggregate)`.
+/// A full channel blocks the stage feeding it, so a slow stage throttles the ones before it.
+pub struct Pipeline<T> {
+    output: Receiver<T>,
+    capacity: usize,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl<T: Send + 'static> Pipeline<T> {
+    pub fn source(items: impl IntoIterator<Item = T> + Send + 'static) -> Self {
+        let (sender, output) = sync_channel(DEFAULT_CAPACITY);
+        let thread = std::thread::spawn(move || {
+            for item in items {
+                // the rest of the pipeline stopped
+                if sender.send(item).is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            output,
+            capacity: DEFAULT_CAPACITY,
+            threads: vec![thread],
+        }
+    }
+    /// Channel size for the stages added after this.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+    pub fn map<U: Send + 'static>(self, mut f: impl FnMut(T) -> U + Send + 'static) -> Pipeline<U> {
+        let Self {
+            output: input,
+            capacity,
+            mut threads,
+        } = self;
+        let (sender, output) = sync_channel(capacity);
+        threads.push(std::thread::spawn(move || {
+            for item in input {
+                if sender.send(f(item)).is_err() {
+                    break;
+                }
+            }
+        }));
+        Pipeline {
+            output,
+            capacity,
+            threads,
+        }
+    }
+    /// Runs `f` on `threads` threads. Items may come out in a different order than they went in.
+    pub fn map_parallel<U: Send + 'static>(
+        self,
+        threads: usize,
+        f: impl Fn(T) -> U + Send + Sync + 'static,
+    ) -> Pipeline<U> {
+        let Self {
+            output: input,
+            capacity,
+            threads: mut handles,
+        } = self;
+        let (sender, output) = sync_channel(capacity);
+        let input = Arc::new(Mutex::new(input));
+        let f = Arc::new(f);
+        for _ in 0..threads.max(1) {
+            let (input, sender, f) = (input.clone(), sender.clone(), f.clone());
+            handles.push(std::thread::spawn(move || loop {
+                let next = input.lock().unwrap_or_else(|e| e.into_inner()).recv();
+                let Ok(item) = next else {
+                    break;
+                };
+                if sender.send(f(item)).is_err() {
+                    break;
+                }
+            }));
+        }
+        Pipeline {
+            output,
+            capacity,
+            threads: handles,
+        }
+    }
+    /// Consumes the output on the calling thread and waits for every stage. A panic in a stage
+    /// is resumed here.
+    pub fn sink(self, mut f: impl FnMut(T)) {
+        for item in self.output {
+            f(item);
+        }
+        for thread in self.threads {
+            if let Err(panic) = thread.join() {
+                std::panic::resume_unwind(panic);
+            }
+        }
+    }
+    pub fn collect(self) -> Vec<T> {
+        let mut result = Vec::new();
+        self.sink(|item| result.push(item));
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_run_stages() {
+        let mut results = Pipeline::source(["1", "2", "x", "4"])
+            .with_capacity(1)
+            .map(|text| text.parse::<u64>().ok())
+            .map_parallel(3, |number| number.map(|n| n * n))
+            .collect();
+        results.sort();
+        assert_eq!(results, [None, Some(1), Some(4), Some(16)]);
+
+        let mut total = 0;
+        Pipeline::source(0..100u64)
+            .map(|n| n + 1)
+            .sink(|n| total += n);
+        assert_eq!(total, 5050);
+    }
+
+    #[test]
+    #[should_panic(expected = "bad stage")]
+    fn should_resume_stage_panic() {
+        Pipeline::source(0..3)
+            .map(|n: i32| if n == 1 { panic!("bad stage") } else { n })
+            .sink(|_| {});
+    }
+}
@@ -1,67 +1,1233 @@
-use std::collections::HashMap;
-use std::fmt::Debug;
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt::{Debug, Display};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use unicode_width::UnicodeWidthStr;
+
+#[cfg(feature = "canvas")]
+use crate::canvas::Style;
+use crate::common::AsF64;
+
+/// Stands in for `canvas::Style` without the `canvas` feature, so nothing is ever highlighted.
+#[cfg(not(feature = "canvas"))]
+#[derive(Clone, Copy)]
+enum Style {}
+
+#[cfg(not(feature = "canvas"))]
+impl Style {
+    fn apply<D: Display>(&self, _content: D) -> String {
+        match *self {}
+    }
+}
+
+#[cfg(feature = "derive")]
+pub use utils_derive::PrettyPrint;
 
 pub trait PrettyPrint {
     fn pretty_print(&self) -> String;
+    /// Collections apply `options` to themselves and to nested collections. Other values
+    /// print as usual.
+    fn pretty_print_with(&self, _options: &Options) -> String {
+        self.pretty_print()
+    }
+    /// Indented, JSON-like output that folds well in editors. It is not meant to be parsed.
+    fn to_pretty_json_like(&self) -> String {
+        self.pretty_print_with(&Options::new().with_json_like())
+    }
+    fn pretty(&self) -> Pretty<'_, Self> {
+        Pretty(self)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Options {
+    limit: Option<(usize, usize)>,
+    json_like: bool,
+    digit_separator: Option<char>,
+    max_width: Option<usize>,
+}
+
+impl Options {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Prints only the first `first` and last `last` entries, with a `… (n more)` line between.
+    pub fn with_limit(mut self, first: usize, last: usize) -> Self {
+        self.limit = Some((first, last));
+        self
+    }
+    /// One entry per line with quoted keys, sets as arrays and `None` as `null`.
+    pub fn with_json_like(mut self) -> Self {
+        self.json_like = true;
+        self
+    }
+    /// Groups the digits of numbers, including numeric map keys. See `group_digits`.
+    pub fn with_digit_separator(mut self, separator: char) -> Self {
+        self.digit_separator = Some(separator);
+        self
+    }
+    /// Wraps lines at `width` columns. Without it, the terminal width is used when printing
+    /// to a terminal.
+    pub fn with_max_width(mut self, width: usize) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+    /// The entries to print, and the position and count of the ones left out.
+    fn truncate<T>(&self, mut entries: Vec<T>) -> (Vec<T>, Option<(usize, usize)>) {
+        match self.limit {
+            Some((first, last)) if entries.len() > first + last => {
+                let omitted = entries.len() - first - last;
+                entries.drain(first..first + omitted);
+                (entries, Some((first, omitted)))
+            }
+            _ => (entries, None),
+        }
+    }
+}
+
+/// Displays a value with `pretty_print`, formatting it only when it is actually written, e.g.
+/// `log::debug!("{}", Pretty(&costs))`.
+pub struct Pretty<'a, T: PrettyPrint + ?Sized>(pub &'a T);
+
+impl<T: PrettyPrint + ?Sized> Display for Pretty<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0.pretty_print())
+    }
 }
 
-fn pprint(sorted_strings: Vec<String>, start_bracket: &str, end_bracket: &str) -> String {
+fn pprint(
+    sorted_strings: Vec<(String, Option<Style>)>,
+    omitted: Option<(usize, usize)>,
+    start_bracket: &str,
+    end_bracket: &str,
+    options: &Options,
+) -> String {
     const MAX_LEN: usize = 125;
-    if sorted_strings.is_empty() {
+    if sorted_strings.is_empty() && omitted.is_none() {
         return format!("{}{}", start_bracket, end_bracket);
     }
+    if options.json_like || sorted_strings.iter().any(|(s, _)| s.contains('\n')) {
+        let mut entries = sorted_strings;
+        if let Some((at, count)) = omitted {
+            entries.insert(at, (omitted_marker(count, options), None));
+        }
+        return pprint_block(entries, start_bracket, end_bracket);
+    }
 
     let max = sorted_strings
         .iter()
-        .map(|s| s.chars().count())
+        .map(|(s, _)| s.width())
         .max()
-        .unwrap();
-    let n = (MAX_LEN / max).max(1);
+        .unwrap_or(1)
+        .max(1);
+    let n = match options.max_width.or_else(terminal_width) {
+        // each entry takes its width plus ", ", and the brackets take two more columns
+        Some(width) => (width.saturating_sub(2) / (max + 2)).max(1),
+        None => (MAX_LEN / max).max(1),
+    };
+    let rows = |entries: &[(String, Option<Style>)]| {
+        entries
+            .chunks(n)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .map(|(s, style)| pad_styled(s, max, *style))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect::<Vec<_>>()
+    };
+    let lines = match omitted {
+        Some((at, count)) => {
+            let mut lines = rows(&sorted_strings[..at]);
+            lines.push(omitted_marker(count, options));
+            lines.extend(rows(&sorted_strings[at..]));
+            lines
+        }
+        None => rows(&sorted_strings),
+    };
     format!(
         "{start} {val} {end}",
         start = start_bracket,
         end = end_bracket,
-        val = sorted_strings
-            .chunks(n)
-            .into_iter()
-            .enumerate()
-            .map(|(i, chunk)| {
-                format!(
-                    "{}{}",
-                    if i == 0 { "" } else { "  " },
-                    chunk
-                        .into_iter()
-                        .map(|s| format!("{:>width$}", s, width = max))
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                )
+        val = lines.join("\n  ")
+    )
+}
+
+#[cfg(all(feature = "canvas", not(test)))]
+fn terminal_width() -> Option<usize> {
+    use std::io::IsTerminal;
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    crossterm::terminal::size()
+        .ok()
+        .map(|(columns, _)| columns as usize)
+}
+
+// tests shouldn't depend on the terminal they happen to run in
+#[cfg(any(not(feature = "canvas"), test))]
+fn terminal_width() -> Option<usize> {
+    None
+}
+
+fn omitted_marker(count: usize, options: &Options) -> String {
+    match options.digit_separator {
+        Some(separator) => format!("… ({} more)", group_digits(count, separator)),
+        None => format!("… ({} more)", count),
+    }
+}
+
+/// One entry per line, for entries that are themselves multi-line collections and for
+/// JSON-like output.
+fn pprint_block(
+    entries: Vec<(String, Option<Style>)>,
+    start_bracket: &str,
+    end_bracket: &str,
+) -> String {
+    let body = entries
+        .into_iter()
+        .map(|(s, style)| {
+            let s = indent(&s);
+            match style {
+                Some(style) => format!("  {}", style.apply(s)),
+                None => format!("  {}", s),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",\n");
+    format!("{}\n{}\n{}", start_bracket, body, end_bracket)
+}
+
+fn indent(text: &str) -> String {
+    text.replace('\n', "\n  ")
+}
+
+/// Right-aligns `text` by display width, so wide characters don't throw off the columns.
+pub(crate) fn pad_left(text: &str, width: usize) -> String {
+    format!("{}{}", " ".repeat(width.saturating_sub(text.width())), text)
+}
+
+/// Like `pad_left`, but only the text itself is styled.
+fn pad_styled(text: &str, width: usize, style: Option<Style>) -> String {
+    match style {
+        Some(style) => format!(
+            "{}{}",
+            " ".repeat(width.saturating_sub(text.width())),
+            style.apply(text)
+        ),
+        None => pad_left(text, width),
+    }
+}
+
+fn pad_right(text: &str, width: usize) -> String {
+    format!("{}{}", text, " ".repeat(width.saturating_sub(text.width())))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+    Center,
+}
+
+impl Align {
+    fn pad(self, text: &str, width: usize) -> String {
+        match self {
+            Align::Left => pad_right(text, width),
+            Align::Right => pad_left(text, width),
+            Align::Center => {
+                let left = width.saturating_sub(text.width()) / 2;
+                pad_right(&format!("{}{}", " ".repeat(left), text), width)
+            }
+        }
+    }
+}
+
+/// A row that can be added to a `Table` in one go.
+pub trait TableRow {
+    fn cells(&self) -> Vec<String>;
+}
+
+impl<A: Display, B: Display> TableRow for (A, B) {
+    fn cells(&self) -> Vec<String> {
+        vec![self.0.to_string(), self.1.to_string()]
+    }
+}
+
+impl<A: Display, B: Display, C: Display> TableRow for (A, B, C) {
+    fn cells(&self) -> Vec<String> {
+        vec![self.0.to_string(), self.1.to_string(), self.2.to_string()]
+    }
+}
+
+impl<A: Display, B: Display, C: Display, D: Display> TableRow for (A, B, C, D) {
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.0.to_string(),
+            self.1.to_string(),
+            self.2.to_string(),
+            self.3.to_string(),
+        ]
+    }
+}
+
+/// Column widths are computed from the contents. Columns without an explicit alignment are
+/// right-aligned when every cell is a number, and left-aligned otherwise.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Table {
+    headers: Vec<String>,
+    aligns: Vec<Option<Align>>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new<H: Display>(headers: impl IntoIterator<Item = H>) -> Self {
+        let headers: Vec<String> = headers.into_iter().map(|h| h.to_string()).collect();
+        Self {
+            aligns: vec![None; headers.len()],
+            headers,
+            rows: vec![],
+        }
+    }
+    pub fn from_rows<H: Display, R: TableRow>(
+        headers: impl IntoIterator<Item = H>,
+        rows: &[R],
+    ) -> Self {
+        let mut table = Self::new(headers);
+        for row in rows {
+            table.rows.push(row.cells());
+        }
+        table
+    }
+    pub fn with_align(mut self, column: usize, align: Align) -> Self {
+        if self.aligns.len() <= column {
+            self.aligns.resize(column + 1, None);
+        }
+        self.aligns[column] = Some(align);
+        self
+    }
+    pub fn with_row<V: Display>(mut self, row: impl IntoIterator<Item = V>) -> Self {
+        self.add_row(row);
+        self
+    }
+    pub fn add_row<V: Display>(&mut self, row: impl IntoIterator<Item = V>) {
+        self.rows
+            .push(row.into_iter().map(|v| v.to_string()).collect());
+    }
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+    fn columns(&self) -> usize {
+        self.rows
+            .iter()
+            .map(|row| row.len())
+            .chain(std::iter::once(self.headers.len()))
+            .max()
+            .unwrap_or(0)
+    }
+    fn align(&self, column: usize) -> Align {
+        self.aligns
+            .get(column)
+            .copied()
+            .flatten()
+            .unwrap_or_else(|| {
+                let numeric = self.rows.iter().all(|row| {
+                    row.get(column)
+                        .is_none_or(|cell| cell.trim().parse::<f64>().is_ok())
+                });
+                if numeric && !self.rows.is_empty() {
+                    Align::Right
+                } else {
+                    Align::Left
+                }
             })
-            .collect::<Vec<_>>()
-            .join("\n")
+    }
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let columns = self.columns();
+        let cell = |row: &[String], column: usize| row.get(column).cloned().unwrap_or_default();
+        let widths: Vec<usize> = (0..columns)
+            .map(|column| {
+                std::iter::once(&self.headers)
+                    .chain(&self.rows)
+                    .map(|row| cell(row, column).width())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let aligns: Vec<Align> = (0..columns).map(|column| self.align(column)).collect();
+        let line = |row: &[String], aligns: &[Align]| {
+            (0..columns)
+                .map(|column| aligns[column].pad(&cell(row, column), widths[column]))
+                .collect::<Vec<_>>()
+                .join(" | ")
+                .trim_end()
+                .to_string()
+        };
+        let mut lines = vec![];
+        if !self.headers.is_empty() {
+            lines.push(line(&self.headers, &aligns));
+            lines.push(
+                widths
+                    .iter()
+                    .map(|&w| "-".repeat(w))
+                    .collect::<Vec<_>>()
+                    .join("-+-"),
+            );
+        }
+        lines.extend(self.rows.iter().map(|row| line(row, &aligns)));
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl PrettyPrint for Table {
+    fn pretty_print(&self) -> String {
+        self.to_string()
+    }
+}
+
+pub(crate) struct Bin {
+    pub(crate) start: f64,
+    pub(crate) end: f64,
+    pub(crate) count: usize,
+}
+
+pub(crate) fn make_bins(values: &[f64], bins: usize) -> Vec<Bin> {
+    let bins = bins.max(1);
+    if values.is_empty() {
+        return vec![];
+    }
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let width = (max - min) / bins as f64;
+    let mut result: Vec<Bin> = (0..bins)
+        .map(|i| Bin {
+            start: min + width * i as f64,
+            end: if i + 1 == bins {
+                max
+            } else {
+                min + width * (i + 1) as f64
+            },
+            count: 0,
+        })
+        .collect();
+    for &value in values {
+        let i = if width > 0.0 {
+            (((value - min) / width) as usize).min(bins - 1)
+        } else {
+            0
+        };
+        result[i].count += 1;
+    }
+    result
+}
+
+pub(crate) fn render_histogram(rows: &[(String, usize)], bar_width: usize) -> String {
+    let label_width = rows.iter().map(|(l, _)| l.width()).max().unwrap_or(0);
+    let max_count = rows.iter().map(|(_, c)| *c).max().unwrap_or(0).max(1);
+    rows.iter()
+        .map(|(label, count)| {
+            let len = (count * bar_width).div_ceil(max_count);
+            format!(
+                "{} | {} {}",
+                pad_left(label, label_width),
+                "█".repeat(len),
+                count
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Bins the finite values and draws one proportional bar per bin, labelled with its range.
+pub fn histogram<T: AsF64>(values: impl IntoIterator<Item = T>, bins: usize) -> String {
+    let values: Vec<f64> = values
+        .into_iter()
+        .map(|v| v.as_f64())
+        .filter(|v| v.is_finite())
+        .collect();
+    let rows: Vec<(String, usize)> = make_bins(&values, bins)
+        .into_iter()
+        .map(|bin| {
+            (
+                format!("{}..{}", short(bin.start), short(bin.end)),
+                bin.count,
+            )
+        })
+        .collect();
+    render_histogram(&rows, 40)
+}
+
+/// At most three decimals, without trailing zeros.
+fn short(value: f64) -> String {
+    let text = format!("{:.3}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+fn pprint_map<K: PrettyPrint, V: PrettyPrint>(
+    kvps: Vec<(&K, &V)>,
+    sort: bool,
+    highlight: impl Fn(&K, &V) -> Option<Style>,
+    options: &Options,
+) -> String {
+    let mut kvps: Vec<_> = kvps
+        .into_iter()
+        .map(|(key, val)| (key.pretty_print_with(options), key, val))
+        .collect();
+    if sort {
+        kvps.sort_by(|a, b| compare_printed(&a.0, &b.0, options));
+    }
+    let (kvps, omitted) = options.truncate(kvps);
+    let texts = kvps
+        .into_iter()
+        .map(|(mut text, key, val)| {
+            if options.json_like && !text.starts_with('"') {
+                text = format!("\"{}\"", text);
+            }
+            (
+                format!("{}: {}", text, val.pretty_print_with(options)),
+                highlight(key, val),
+            )
+        })
+        .collect();
+    pprint(texts, omitted, "{", "}", options)
+}
+
+fn pprint_values<T: PrettyPrint>(
+    values: Vec<&T>,
+    sort: bool,
+    start_bracket: &str,
+    end_bracket: &str,
+    highlight: impl Fn(&T) -> Option<Style>,
+    options: &Options,
+) -> String {
+    let mut texts: Vec<_> = values
+        .into_iter()
+        .map(|val| (val.pretty_print_with(options), highlight(val)))
+        .collect();
+    if sort {
+        texts.sort_by(|a, b| compare_printed(&a.0, &b.0, options));
+    }
+    let (texts, omitted) = options.truncate(texts);
+    if options.json_like {
+        pprint(texts, omitted, "[", "]", options)
+    } else {
+        pprint(texts, omitted, start_bracket, end_bracket, options)
+    }
+}
+
+/// Orders entries by their printed text, so elements don't have to be `Ord`. Runs of digits
+/// compare by value, so numbers sort numerically, also inside tuples and nested collections.
+fn compare_printed(a: &str, b: &str, options: &Options) -> Ordering {
+    let number = |text: &str| match options.digit_separator {
+        Some(separator) => text.replace(separator, "").parse::<f64>().ok(),
+        None => text.parse::<f64>().ok(),
+    };
+    if let (Some(x), Some(y)) = (number(a), number(b)) {
+        return x.total_cmp(&y).then_with(|| a.cmp(b));
+    }
+    fn digit_run(text: &str) -> (&str, &str) {
+        let end = text
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(text.len());
+        let (run, rest) = text.split_at(end);
+        (run.trim_start_matches('0'), rest)
+    }
+    let (mut a, mut b) = (a, b);
+    loop {
+        let (x, y) = match (a.chars().next(), b.chars().next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => (x, y),
+        };
+        let ordering = if x.is_ascii_digit() && y.is_ascii_digit() {
+            let ((x, rest_a), (y, rest_b)) = (digit_run(a), digit_run(b));
+            (a, b) = (rest_a, rest_b);
+            x.len().cmp(&y.len()).then_with(|| x.cmp(y))
+        } else {
+            (a, b) = (&a[x.len_utf8()..], &b[y.len_utf8()..]);
+            x.cmp(&y)
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+/// Prints map entries like `HashMap::pretty_print`, styling the entries `highlight` picks out.
+#[cfg(feature = "canvas")]
+pub fn pretty_print_map_highlighted<'a, K: PrettyPrint + 'a, V: PrettyPrint + 'a>(
+    entries: impl IntoIterator<Item = (&'a K, &'a V)>,
+    highlight: impl Fn(&K, &V) -> Option<Style>,
+) -> String {
+    pprint_map(
+        entries.into_iter().collect(),
+        true,
+        highlight,
+        &Options::default(),
+    )
+}
+
+/// Prints values like `Vec::pretty_print`, styling the values `highlight` picks out.
+#[cfg(feature = "canvas")]
+pub fn pretty_print_highlighted<'a, T: PrettyPrint + 'a>(
+    values: impl IntoIterator<Item = &'a T>,
+    highlight: impl Fn(&T) -> Option<Style>,
+) -> String {
+    pprint_values(
+        values.into_iter().collect(),
+        true,
+        "[",
+        "]",
+        highlight,
+        &Options::default(),
+    )
+}
+
+/// Orderings other than by key for maps. `pretty_print` always sorts by key.
+pub trait PrettyPrintMap<K, V> {
+    fn pretty_print_by(&self, compare: impl FnMut((&K, &V), (&K, &V)) -> Ordering) -> String;
+    /// Entries in the map's own iteration order.
+    fn pretty_print_unsorted(&self) -> String;
+    fn pretty_print_by_value(&self) -> String
+    where
+        V: Ord,
+    {
+        self.pretty_print_by(|a, b| a.1.cmp(b.1))
+    }
+    fn pretty_print_by_value_desc(&self) -> String
+    where
+        V: Ord,
+    {
+        self.pretty_print_by(|a, b| b.1.cmp(a.1))
+    }
+}
+
+fn map_by<'a, K: PrettyPrint + 'a, V: PrettyPrint + 'a>(
+    kvps: impl Iterator<Item = (&'a K, &'a V)>,
+    mut compare: impl FnMut((&K, &V), (&K, &V)) -> Ordering,
+) -> String {
+    let mut kvps = kvps.collect::<Vec<_>>();
+    kvps.sort_by(|a, b| compare(*a, *b));
+    pprint_map(kvps, false, |_, _| None, &Options::default())
+}
+
+impl<K: PrettyPrint, V: PrettyPrint> PrettyPrintMap<K, V> for HashMap<K, V> {
+    fn pretty_print_by(&self, compare: impl FnMut((&K, &V), (&K, &V)) -> Ordering) -> String {
+        map_by(self.iter(), compare)
+    }
+    fn pretty_print_unsorted(&self) -> String {
+        pprint_map(
+            self.iter().collect(),
+            false,
+            |_, _| None,
+            &Options::default(),
+        )
+    }
+}
+
+impl<K: PrettyPrint, V: PrettyPrint> PrettyPrintMap<K, V> for BTreeMap<K, V> {
+    fn pretty_print_by(&self, compare: impl FnMut((&K, &V), (&K, &V)) -> Ordering) -> String {
+        map_by(self.iter(), compare)
+    }
+    fn pretty_print_unsorted(&self) -> String {
+        pprint_map(
+            self.iter().collect(),
+            false,
+            |_, _| None,
+            &Options::default(),
+        )
+    }
+}
+
+/// Orderings other than the natural one for sequences and sets.
+pub trait PrettyPrintValues<T> {
+    fn pretty_print_by(&self, compare: impl FnMut(&T, &T) -> Ordering) -> String;
+    /// Values in the collection's own iteration order, e.g. insertion order for a `Vec`.
+    fn pretty_print_unsorted(&self) -> String;
+}
+
+fn values_by<'a, T: PrettyPrint + 'a>(
+    values: impl Iterator<Item = &'a T>,
+    start_bracket: &str,
+    end_bracket: &str,
+    mut compare: impl FnMut(&T, &T) -> Ordering,
+) -> String {
+    let mut s: Vec<_> = values.collect();
+    s.sort_by(|a, b| compare(a, b));
+    pprint_values(
+        s,
+        false,
+        start_bracket,
+        end_bracket,
+        |_| None,
+        &Options::default(),
     )
 }
 
-impl<T: Ord + Debug, V: Debug> PrettyPrint for HashMap<T, V> {
+fn values_unsorted<'a, T: PrettyPrint + 'a>(
+    values: impl Iterator<Item = &'a T>,
+    start_bracket: &str,
+    end_bracket: &str,
+) -> String {
+    pprint_values(
+        values.collect(),
+        false,
+        start_bracket,
+        end_bracket,
+        |_| None,
+        &Options::default(),
+    )
+}
+
+impl<T: PrettyPrint> PrettyPrintValues<T> for [T] {
+    fn pretty_print_by(&self, compare: impl FnMut(&T, &T) -> Ordering) -> String {
+        values_by(self.iter(), "[", "]", compare)
+    }
+    fn pretty_print_unsorted(&self) -> String {
+        values_unsorted(self.iter(), "[", "]")
+    }
+}
+
+impl<T: PrettyPrint> PrettyPrintValues<T> for Vec<T> {
+    fn pretty_print_by(&self, compare: impl FnMut(&T, &T) -> Ordering) -> String {
+        self.as_slice().pretty_print_by(compare)
+    }
+    fn pretty_print_unsorted(&self) -> String {
+        self.as_slice().pretty_print_unsorted()
+    }
+}
+
+impl<T: PrettyPrint> PrettyPrintValues<T> for VecDeque<T> {
+    fn pretty_print_by(&self, compare: impl FnMut(&T, &T) -> Ordering) -> String {
+        values_by(self.iter(), "[", "]", compare)
+    }
+    fn pretty_print_unsorted(&self) -> String {
+        values_unsorted(self.iter(), "[", "]")
+    }
+}
+
+impl<T: PrettyPrint> PrettyPrintValues<T> for HashSet<T> {
+    fn pretty_print_by(&self, compare: impl FnMut(&T, &T) -> Ordering) -> String {
+        values_by(self.iter(), "{", "}", compare)
+    }
+    fn pretty_print_unsorted(&self) -> String {
+        values_unsorted(self.iter(), "{", "}")
+    }
+}
+
+impl<T: PrettyPrint> PrettyPrintValues<T> for BTreeSet<T> {
+    fn pretty_print_by(&self, compare: impl FnMut(&T, &T) -> Ordering) -> String {
+        values_by(self.iter(), "{", "}", compare)
+    }
+    fn pretty_print_unsorted(&self) -> String {
+        values_unsorted(self.iter(), "{", "}")
+    }
+}
+
+impl<K: PrettyPrint, V: PrettyPrint> PrettyPrint for HashMap<K, V> {
     fn pretty_print(&self) -> String {
-        let mut kvps = self.iter().collect::<Vec<_>>();
-        kvps.sort_by_key(|kvp| kvp.0);
-        let texts: Vec<String> = kvps
-            .into_iter()
-            .map(|(key, val)| format!("{:?}: {:?}", key, val))
-            .collect();
-        pprint(texts, "{", "}")
+        self.pretty_print_with(&Options::default())
+    }
+    fn pretty_print_with(&self, options: &Options) -> String {
+        pprint_map(self.iter().collect(), true, |_, _| None, options)
+    }
+}
+
+impl<K: PrettyPrint, V: PrettyPrint> PrettyPrint for BTreeMap<K, V> {
+    fn pretty_print(&self) -> String {
+        self.pretty_print_with(&Options::default())
+    }
+    fn pretty_print_with(&self, options: &Options) -> String {
+        pprint_map(self.iter().collect(), true, |_, _| None, options)
+    }
+}
+
+/// Implements `PrettyPrint` for a sequence or set type, sorted by the printed elements.
+macro_rules! impl_pretty_print_values {
+    ($([$($generics:tt)*] $collection:ty => $start:literal $end:literal),*) => {$(
+        impl<$($generics)*> PrettyPrint for $collection
+        where
+            T: PrettyPrint,
+        {
+            fn pretty_print(&self) -> String {
+                self.pretty_print_with(&Options::default())
+            }
+            fn pretty_print_with(&self, options: &Options) -> String {
+                pprint_values(self.iter().collect(), true, $start, $end, |_| None, options)
+            }
+        }
+    )*};
+}
+
+impl_pretty_print_values!(
+    [T] Vec<T> => "[" "]",
+    [T] [T] => "[" "]",
+    [T, const N: usize] [T; N] => "[" "]",
+    [T] VecDeque<T> => "[" "]",
+    [T] HashSet<T> => "{" "}",
+    [T] BTreeSet<T> => "{" "}"
+);
+
+impl<T: PrettyPrint + ?Sized> PrettyPrint for &T {
+    fn pretty_print(&self) -> String {
+        (**self).pretty_print()
+    }
+    fn pretty_print_with(&self, options: &Options) -> String {
+        (**self).pretty_print_with(options)
+    }
+}
+
+impl PrettyPrint for str {
+    fn pretty_print(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl PrettyPrint for String {
+    fn pretty_print(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl PrettyPrint for bool {
+    fn pretty_print(&self) -> String {
+        self.to_string()
     }
 }
 
-impl<T: Ord + Debug> PrettyPrint for Vec<T> {
+impl PrettyPrint for char {
     fn pretty_print(&self) -> String {
-        let mut s: Vec<_> = self.iter().collect();
-        s.sort();
-        let texts: Vec<String> = s.into_iter().map(|val| format!("{:?}", val)).collect();
-        pprint(texts, "[", "]")
+        format!("{:?}", self)
+    }
+}
+
+macro_rules! impl_pretty_print_number {
+    ($($t:ty),*) => {
+        $(
+            impl PrettyPrint for $t {
+                fn pretty_print(&self) -> String {
+                    format!("{:?}", self)
+                }
+                fn pretty_print_with(&self, options: &Options) -> String {
+                    match options.digit_separator {
+                        Some(separator) => group_digits(self, separator),
+                        None => self.pretty_print(),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_pretty_print_number!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+/// Inserts `separator` between groups of three digits in the integer part of a number, e.g.
+/// `1_234_567.891`. Anything that isn't a number is returned unchanged.
+pub fn group_digits(n: impl Display, separator: char) -> String {
+    let text = n.to_string();
+    let (sign, rest) = match text.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", text.as_str()),
+    };
+    let (integer, fraction) = match rest.find(['.', 'e', 'E']) {
+        Some(i) => rest.split_at(i),
+        None => (rest, ""),
+    };
+    if integer.is_empty() || !integer.chars().all(|c| c.is_ascii_digit()) {
+        return text;
+    }
+    let mut grouped = String::new();
+    for (i, ch) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i).is_multiple_of(3) {
+            grouped.push(separator);
+        }
+        grouped.push(ch);
+    }
+    format!("{}{}{}", sign, grouped, fraction)
+}
+
+fn pprint_tuple(parts: &[String], options: &Options) -> String {
+    if options.json_like {
+        format!("[{}]", parts.join(", "))
+    } else {
+        format!("({})", parts.join(", "))
     }
 }
 
+macro_rules! impl_pretty_print_tuple {
+    ($(($($t:ident $i:tt),*)),*) => {$(
+        impl<$($t: PrettyPrint),*> PrettyPrint for ($($t,)*) {
+            fn pretty_print(&self) -> String {
+                self.pretty_print_with(&Options::default())
+            }
+            fn pretty_print_with(&self, options: &Options) -> String {
+                pprint_tuple(&[$(self.$i.pretty_print_with(options)),*], options)
+            }
+        }
+    )*};
+}
+
+impl_pretty_print_tuple!(
+    (A 0, B 1),
+    (A 0, B 1, C 2),
+    (A 0, B 1, C 2, D 3),
+    (A 0, B 1, C 2, D 3, E 4),
+    (A 0, B 1, C 2, D 3, E 4, F 5)
+);
+
+/// Implements `PrettyPrint` with the type's `Debug` output, for std types that are common
+/// inside collections and derived structs.
+macro_rules! impl_pretty_print_debug {
+    ($($t:ty),*) => {$(
+        impl PrettyPrint for $t {
+            fn pretty_print(&self) -> String {
+                format!("{:?}", self)
+            }
+        }
+    )*};
+}
+
+impl_pretty_print_debug!((), Duration, Path, PathBuf);
+
+impl<T: PrettyPrint> PrettyPrint for Option<T> {
+    fn pretty_print(&self) -> String {
+        self.pretty_print_with(&Options::default())
+    }
+    fn pretty_print_with(&self, options: &Options) -> String {
+        match self {
+            Some(value) if options.json_like => value.pretty_print_with(options),
+            Some(value) => format!("Some({})", value.pretty_print_with(options)),
+            None if options.json_like => "null".to_string(),
+            None => "None".to_string(),
+        }
+    }
+}
+
+/// Count, min, max and mean of a numeric collection, for when listing it would be too long.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+}
+
+impl Summary {
+    pub fn of<T: AsF64>(values: impl IntoIterator<Item = T>) -> Option<Self> {
+        let mut summary = Self {
+            count: 0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            mean: 0.0,
+        };
+        let mut sum = 0.0;
+        for value in values {
+            let value = value.as_f64();
+            summary.count += 1;
+            summary.min = summary.min.min(value);
+            summary.max = summary.max.max(value);
+            sum += value;
+        }
+        if summary.count == 0 {
+            return None;
+        }
+        summary.mean = sum / summary.count as f64;
+        Some(summary)
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ count: {}, min: {}, max: {}, mean: {:.2} }}",
+            self.count, self.min, self.max, self.mean
+        )
+    }
+}
+
+impl PrettyPrint for Summary {
+    fn pretty_print(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// Prints `name { key: value }` as an aligned block, one field per line.
+pub fn pretty_print_fields(name: &str, fields: &[(&str, String)]) -> String {
+    pretty_print_fields_with(name, None, fields, &Options::default())
+}
+
+/// Used by `#[derive(PrettyPrint)]`. In JSON-like mode structs become objects of their fields
+/// and enum variants are tagged with their name, e.g. `{ "Circle": { "radius": 1.5 } }`.
+pub fn pretty_print_fields_with(
+    name: &str,
+    variant: Option<&str>,
+    fields: &[(&str, String)],
+    options: &Options,
+) -> String {
+    if options.json_like {
+        let object = pprint_block(
+            fields
+                .iter()
+                .map(|(key, value)| (format!("\"{}\": {}", key, value), None))
+                .collect(),
+            "{",
+            "}",
+        );
+        return match variant {
+            Some(variant) if fields.is_empty() => format!("\"{}\"", variant),
+            Some(variant) => pprint_block(
+                vec![(format!("\"{}\": {}", variant, object), None)],
+                "{",
+                "}",
+            ),
+            None if fields.is_empty() => "{}".to_string(),
+            None => object,
+        };
+    }
+    let name = match variant {
+        Some(variant) => format!("{}::{}", name, variant),
+        None => name.to_string(),
+    };
+    if fields.is_empty() {
+        return name;
+    }
+    let key_width = fields.iter().map(|(key, _)| key.width()).max().unwrap_or(0) + 1;
+    let body = fields
+        .iter()
+        .map(|(key, value)| {
+            format!(
+                "  {} {}",
+                pad_right(&format!("{}:", key), key_width),
+                value.replace('\n', "\n  ")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("{} {{\n{}\n}}", name, body)
+}
+
+/// The error's message followed by its indented chain of causes, like anyhow's `{:?}` output.
+/// Pass an `anyhow::Error` as `error.as_ref()`.
+pub fn pretty_print_error(error: &dyn Error) -> String {
+    let mut result = error.to_string();
+    let causes: Vec<String> =
+        std::iter::successors(error.source(), |&e: &&(dyn Error + 'static)| e.source())
+            .map(|e| e.to_string())
+            .collect();
+    if !causes.is_empty() {
+        result.push_str("\n\nCaused by:");
+        let width = digits(causes.len() - 1);
+        for (i, cause) in causes.iter().enumerate() {
+            let indent = " ".repeat(width + 4);
+            result.push_str(&format!(
+                "\n  {}: {}",
+                pad_left(&i.to_string(), width),
+                cause.replace('\n', &format!("\n{}", indent))
+            ));
+        }
+    }
+    result
+}
+
+/// Like `pretty_print_error`, followed by at most `max_frames` frames of `backtrace`. Frames
+/// inside the standard library and the panic/backtrace machinery are left out.
+pub fn pretty_print_error_with_backtrace(
+    error: &dyn Error,
+    backtrace: &Backtrace,
+    max_frames: usize,
+) -> String {
+    let mut result = pretty_print_error(error);
+    if backtrace.status() != BacktraceStatus::Captured {
+        return result;
+    }
+    let text = backtrace.to_string();
+    let mut frames: Vec<Vec<&str>> = vec![];
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        let is_frame = trimmed
+            .split_once(':')
+            .is_some_and(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+        match frames.last_mut() {
+            Some(frame) if !is_frame => frame.push(trimmed),
+            _ => frames.push(vec![trimmed]),
+        }
+    }
+    const HIDDEN: [&str; 6] = [
+        "std::",
+        "core::",
+        "alloc::",
+        "__rust",
+        "rust_begin_unwind",
+        "backtrace::",
+    ];
+    let frames: Vec<&Vec<&str>> = frames
+        .iter()
+        .filter(|frame| {
+            let name = frame[0].split_once(": ").map_or(frame[0], |(_, name)| name);
+            !HIDDEN.iter().any(|hidden| name.starts_with(hidden))
+        })
+        .collect();
+    result.push_str("\n\nStack backtrace:");
+    for frame in frames.iter().take(max_frames) {
+        result.push_str(&format!("\n  {}", frame.join("\n      ")));
+    }
+    if frames.len() > max_frames {
+        result.push_str(&format!(
+            "\n  {}",
+            omitted_marker(frames.len() - max_frames, &Options::default())
+        ));
+    }
+    result
+}
+
+type CellHighlight<'a, T> = Box<dyn Fn(usize, usize, &T) -> Option<Style> + 'a>;
+
+/// How `pretty_print_grid` lays out a grid. Cells are separated by a space unless every
+/// formatted cell is a single column wide.
+pub struct GridFormat<'a, T> {
+    row_labels: bool,
+    column_labels: bool,
+    format_cell: Box<dyn Fn(&T) -> String + 'a>,
+    highlight: Option<CellHighlight<'a, T>>,
+}
+
+impl<'a, T: Display> GridFormat<'a, T> {
+    pub fn new() -> Self {
+        Self::with_formatter(|cell: &T| cell.to_string())
+    }
+}
+
+impl<T: Display> Default for GridFormat<'_, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T> GridFormat<'a, T> {
+    pub fn with_formatter(format_cell: impl Fn(&T) -> String + 'a) -> Self {
+        Self {
+            row_labels: false,
+            column_labels: false,
+            format_cell: Box::new(format_cell),
+            highlight: None,
+        }
+    }
+    pub fn with_row_labels(mut self, row_labels: bool) -> Self {
+        self.row_labels = row_labels;
+        self
+    }
+    /// Column indices wider than the cells are written vertically, one digit per line.
+    pub fn with_column_labels(mut self, column_labels: bool) -> Self {
+        self.column_labels = column_labels;
+        self
+    }
+    pub fn with_labels(self) -> Self {
+        self.with_row_labels(true).with_column_labels(true)
+    }
+    /// Styles the cells for which `highlight(x, y, cell)` returns a style.
+    #[cfg(feature = "canvas")]
+    pub fn with_highlight(
+        mut self,
+        highlight: impl Fn(usize, usize, &T) -> Option<Style> + 'a,
+    ) -> Self {
+        self.highlight = Some(Box::new(highlight));
+        self
+    }
+}
+
+pub fn pretty_print_grid<T, R: AsRef<[T]>>(grid: &[R], format: &GridFormat<T>) -> String {
+    let cells: Vec<Vec<String>> = grid
+        .iter()
+        .map(|row| row.as_ref().iter().map(&format.format_cell).collect())
+        .collect();
+    let style = |x: usize, y: usize| {
+        format
+            .highlight
+            .as_ref()
+            .and_then(|highlight| highlight(x, y, &grid[y].as_ref()[x]))
+    };
+    let columns = cells.iter().map(|row| row.len()).max().unwrap_or(0);
+    let cell_width = cells
+        .iter()
+        .flatten()
+        .map(|cell| cell.width())
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let separator = if cell_width > 1 { " " } else { "" };
+    let label_width = if format.row_labels {
+        digits(cells.len().saturating_sub(1))
+    } else {
+        0
+    };
+    let join = |label: String, row: Vec<String>| {
+        let mut line = if format.row_labels {
+            format!("{} ", pad_left(&label, label_width))
+        } else {
+            String::new()
+        };
+        line.push_str(&row.join(separator));
+        line.trim_end().to_string()
+    };
+
+    let mut lines = vec![];
+    if format.column_labels && columns > 0 {
+        let column_digits = digits(columns - 1);
+        if column_digits <= cell_width {
+            let labels = (0..columns)
+                .map(|x| pad_left(&x.to_string(), cell_width))
+                .collect();
+            lines.push(join(String::new(), labels));
+        } else {
+            for d in 0..column_digits {
+                let place = 10usize.pow((column_digits - 1 - d) as u32);
+                let labels = (0..columns)
+                    .map(|x| {
+                        let digit = if x >= place || place == 1 {
+                            ((x / place) % 10).to_string()
+                        } else {
+                            String::new()
+                        };
+                        pad_left(&digit, cell_width)
+                    })
+                    .collect();
+                lines.push(join(String::new(), labels));
+            }
+        }
+    }
+    for (y, row) in cells.into_iter().enumerate() {
+        let row = row
+            .iter()
+            .enumerate()
+            .map(|(x, cell)| pad_styled(cell, cell_width, style(x, y)))
+            .collect();
+        lines.push(join(y.to_string(), row));
+    }
+    lines.join("\n")
+}
+
+fn digits(n: usize) -> usize {
+    n.to_string().len()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -106,4 +1272,309 @@ mod tests {
         assert_eq!(vec.pretty_print(), "[      1,      2,      3,      4,      5,     13,     24,     52,    123,    253,    256,    421,    432,    752,   1223,   4235,   4321,   7563,  32423,  42314
    43214, 342432, 453212 ]");
     }
+
+    #[test]
+    fn should_print_nested_collections() {
+        let map: HashMap<usize, Vec<&str>> =
+            [(2, vec!["b", "a"]), (1, vec![])].into_iter().collect();
+        assert_eq!(
+            map.pretty_print(),
+            r#"{           1: [], 2: [ "a", "b" ] }"#
+        );
+
+        let inner: Vec<usize> = (0..200).collect();
+        let map: BTreeMap<&str, Vec<usize>> = [("x", inner), ("y", vec![1])].into_iter().collect();
+        let printed = map.pretty_print();
+        assert!(printed.starts_with("{\n  \"x\": [   0,   1,"));
+        assert!(printed.contains(" 40\n     41,"));
+        assert!(printed.ends_with(" 199 ],\n  \"y\": [ 1 ]\n}"));
+
+        let rows = vec![vec![(1, 'a')], vec![(0, 'b'), (2, 'c')]];
+        assert_eq!(
+            rows.pretty_print(),
+            "[ [ (0, 'b'), (2, 'c') ],           [ (1, 'a') ] ]"
+        );
+
+        let maps: Vec<HashMap<&str, usize>> =
+            vec![[("b", 10), ("a", 9)].into_iter().collect(), HashMap::new()];
+        assert_eq!(
+            maps.pretty_print(),
+            r#"[ {  "a": 9, "b": 10 },                   {} ]"#
+        );
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    enum TestError {
+        #[error("could not load config")]
+        Load(#[source] std::io::Error),
+    }
+
+    #[test]
+    fn should_wrap_at_max_width() {
+        let vec: Vec<usize> = (10..20).collect();
+        let options = Options::new().with_max_width(24);
+        assert_eq!(
+            vec.pretty_print_with(&options),
+            "[ 10, 11, 12, 13, 14\n  15, 16, 17, 18, 19 ]"
+        );
+        assert!(vec
+            .pretty_print_with(&options)
+            .lines()
+            .all(|line| line.width() <= 24));
+        assert_eq!(
+            vec[..1].pretty_print_with(&Options::new().with_max_width(1)),
+            "[ 10 ]"
+        );
+    }
+
+    #[test]
+    fn should_group_digits() {
+        assert_eq!(group_digits(1234567, '_'), "1_234_567");
+        assert_eq!(group_digits(-1234, ','), "-1,234");
+        assert_eq!(group_digits(123, ','), "123");
+        assert_eq!(group_digits(12345.678, ','), "12,345.678");
+        assert_eq!(group_digits("abc", ','), "abc");
+
+        let map: HashMap<u64, u64> = [(1000, 2500000), (7, 12)].into_iter().collect();
+        let options = Options::new().with_digit_separator(',');
+        assert_eq!(
+            map.pretty_print_with(&options),
+            "{            7: 12, 1,000: 2,500,000 }"
+        );
+        let vec: Vec<usize> = (0..12346).collect();
+        assert!(vec
+            .pretty_print_with(&options.with_limit(1, 0))
+            .ends_with("… (12,345 more) ]"));
+    }
+
+    #[test]
+    fn should_print_error_chain() {
+        let io = std::io::Error::other("disk on fire\nand smoking");
+        let error = TestError::Load(io);
+        assert_eq!(
+            pretty_print_error(&error),
+            "could not load config\n\nCaused by:\n  0: disk on fire\n     and smoking"
+        );
+        assert_eq!(
+            pretty_print_error(&std::fmt::Error),
+            "an error occurred when formatting an argument"
+        );
+
+        let backtrace = Backtrace::force_capture();
+        let printed = pretty_print_error_with_backtrace(&error, &backtrace, 1);
+        assert!(printed.contains("\n\nStack backtrace:\n  "));
+        assert!(!printed.contains("std::backtrace"));
+        assert_eq!(
+            pretty_print_error_with_backtrace(&error, &Backtrace::disabled(), 1),
+            pretty_print_error(&error)
+        );
+    }
+
+    #[test]
+    fn should_render_histogram() {
+        let values = [1, 2, 2, 3, 3, 3, 4, 4, 4, 4];
+        assert_eq!(
+            histogram(values, 3),
+            "1..2 | ██████ 1\n2..3 | ████████████ 2\n3..4 | ████████████████████████████████████████ 7"
+        );
+        assert_eq!(
+            histogram([0.5, f64::NAN, 0.25], 1),
+            "0.25..0.5 | ████████████████████████████████████████ 2"
+        );
+        assert_eq!(histogram(Vec::<f64>::new(), 3), "");
+    }
+
+    #[test]
+    fn should_print_json_like() {
+        let map: BTreeMap<usize, Vec<&str>> =
+            [(1, vec!["b", "a"]), (2, vec![])].into_iter().collect();
+        assert_eq!(
+            map.to_pretty_json_like(),
+            "{\n  \"1\": [\n    \"a\",\n    \"b\"\n  ],\n  \"2\": []\n}"
+        );
+        let options = vec![Some("a"), None];
+        assert_eq!(options.to_pretty_json_like(), "[\n  \"a\",\n  null\n]");
+        assert_eq!(options.pretty_print(), r#"[      None, Some("a") ]"#);
+        let limits: HashMap<&str, Option<usize>> =
+            [("b", None), ("a", Some(3))].into_iter().collect();
+        assert_eq!(
+            limits.to_pretty_json_like(),
+            "{\n  \"a\": 3,\n  \"b\": null\n}"
+        );
+        let set: BTreeSet<(i32, char)> = [(1, 'x')].into_iter().collect();
+        assert_eq!(set.to_pretty_json_like(), "[\n  [1, 'x']\n]");
+        assert_eq!(
+            pretty_print_fields_with(
+                "Shape",
+                Some("Circle"),
+                &[("radius", "1.5".to_string())],
+                &Options::new().with_json_like()
+            ),
+            "{\n  \"Circle\": {\n    \"radius\": 1.5\n  }\n}"
+        );
+    }
+
+    #[test]
+    fn should_only_count_entries_at_limit_zero() {
+        let options = Options::new().with_limit(0, 0);
+        assert_eq!(vec![3, 1, 2].pretty_print_with(&options), "[ … (3 more) ]");
+        let map: HashMap<usize, usize> = [(1, 2)].into_iter().collect();
+        assert_eq!(map.pretty_print_with(&options), "{ … (1 more) }");
+    }
+
+    #[test]
+    fn should_truncate_long_collections() {
+        let vec: Vec<usize> = (0..1000).collect();
+        let options = Options::new().with_limit(3, 2);
+        assert_eq!(
+            vec.pretty_print_with(&options),
+            "[   0,   1,   2\n  … (995 more)\n  998, 999 ]"
+        );
+        assert_eq!(vec[..5].pretty_print_with(&options), "[ 0, 1, 2, 3, 4 ]");
+
+        let map: HashMap<usize, Vec<usize>> =
+            [(1, vec![5, 6, 7]), (2, vec![])].into_iter().collect();
+        assert_eq!(
+            map.pretty_print_with(&Options::new().with_limit(1, 0)),
+            "{\n  1: [ 5\n    … (2 more) ],\n  … (1 more)\n}"
+        );
+
+        let summary = Summary::of(vec.iter().copied()).unwrap();
+        assert_eq!(
+            summary.to_string(),
+            "{ count: 1000, min: 0, max: 999, mean: 499.50 }"
+        );
+        assert_eq!(Summary::of(Vec::<f64>::new()), None);
+    }
+
+    #[test]
+    fn should_sort_by_strategy() {
+        let counts: HashMap<&str, usize> = [("a", 2), ("b", 7), ("c", 1)].into_iter().collect();
+        assert_eq!(
+            counts.pretty_print_by_value_desc(),
+            r#"{ "b": 7, "a": 2, "c": 1 }"#
+        );
+        assert_eq!(
+            counts.pretty_print_by_value(),
+            r#"{ "c": 1, "a": 2, "b": 7 }"#
+        );
+        let counts: BTreeMap<&str, usize> = counts.into_iter().collect();
+        assert_eq!(
+            counts.pretty_print_by(|a, b| b.0.cmp(a.0)),
+            r#"{ "c": 1, "b": 7, "a": 2 }"#
+        );
+
+        let vec: Vec<f64> = vec![3.5, -1.0, 2.0];
+        assert_eq!(vec.pretty_print_unsorted(), "[  3.5, -1.0,  2.0 ]");
+        assert_eq!(
+            vec.pretty_print_by(|a, b| a.total_cmp(b)),
+            "[ -1.0,  2.0,  3.5 ]"
+        );
+    }
+
+    #[test]
+    fn should_display_through_adapter() {
+        let vec = vec![3, 1, 2];
+        assert_eq!(format!("{}", Pretty(&vec)), "[ 1, 2, 3 ]");
+        assert_eq!(format!("{}", vec[..2].pretty()), "[ 1, 3 ]");
+    }
+
+    #[test]
+    fn should_print_other_collections() {
+        let map: BTreeMap<&str, usize> = [("b", 2), ("a", 1)].into_iter().collect();
+        assert_eq!(map.pretty_print(), r#"{ "a": 1, "b": 2 }"#);
+
+        let set: HashSet<usize> = [3, 1, 2].into_iter().collect();
+        assert_eq!(set.pretty_print(), "{ 1, 2, 3 }");
+        let set: BTreeSet<usize> = set.into_iter().collect();
+        assert_eq!(set.pretty_print(), "{ 1, 2, 3 }");
+
+        let deque: VecDeque<i32> = [10, -5].into_iter().collect();
+        assert_eq!(deque.pretty_print(), "[ -5, 10 ]");
+        assert_eq!([3, 1].pretty_print(), "[ 1, 3 ]");
+        assert_eq!([3, 1][..1].pretty_print(), "[ 3 ]");
+
+        assert_eq!(Some(vec![2, 1]).pretty_print(), "Some([ 1, 2 ])");
+
+        let vec = vec![10.5, -2.0, 3.0];
+        assert_eq!(vec.pretty_print(), "[ -2.0,  3.0, 10.5 ]");
+        let names = vec!["a10", "a9", "b"];
+        assert_eq!(names.pretty_print(), r#"[  "a9", "a10",   "b" ]"#);
+        let pairs = vec![
+            (1, 2, Duration::from_millis(5), "x"),
+            (0, 9, Duration::ZERO, "y"),
+        ];
+        assert_eq!(
+            pairs.pretty_print(),
+            r#"[ (0, 9, 0ns, "y"), (1, 2, 5ms, "x") ]"#
+        );
+        assert_eq!(None::<Vec<usize>>.pretty_print(), "None");
+    }
+
+    #[test]
+    fn should_print_grid() {
+        let grid: Vec<Vec<char>> = ["#..", ".#."].iter().map(|r| r.chars().collect()).collect();
+        assert_eq!(pretty_print_grid(&grid, &GridFormat::new()), "#..\n.#.");
+
+        let grid = vec![vec!['.'; 12]; 11];
+        let printed = pretty_print_grid(&grid, &GridFormat::new().with_labels());
+        let lines: Vec<&str> = printed.lines().collect();
+        assert_eq!(lines[0], "             11");
+        assert_eq!(lines[1], "   012345678901");
+        assert_eq!(lines[2], " 0 ............");
+        assert_eq!(lines[12], "10 ............");
+
+        let grid = [[1, 20], [300, 4]];
+        let format = GridFormat::with_formatter(|n: &i32| format!("{:x}", n)).with_labels();
+        assert_eq!(
+            pretty_print_grid(&grid, &format),
+            "    0   1\n0   1  14\n1 12c   4"
+        );
+    }
+
+    #[cfg(feature = "canvas")]
+    #[test]
+    fn should_highlight_entries() {
+        let red = Style::new().fg(crate::canvas::Color::Red);
+        let map: HashMap<usize, usize> = [(1, 10), (2, 20)].into_iter().collect();
+        assert_eq!(
+            pretty_print_map_highlighted(&map, |&key, _| (key == 2).then_some(red)),
+            format!("{{ 1: 10, {} }}", red.apply("2: 20"))
+        );
+        assert_eq!(
+            pretty_print_highlighted(&[3, 10], |&n| (n > 5).then_some(red)),
+            format!("[  3, {} ]", red.apply("10"))
+        );
+
+        let grid = [['.', '#'], ['#', '.']];
+        let format = GridFormat::new().with_highlight(|x, y, _| (x == y).then_some(red));
+        assert_eq!(
+            pretty_print_grid(&grid, &format),
+            format!("{}#\n#{}", red.apply('.'), red.apply('.'))
+        );
+    }
+
+    #[test]
+    fn should_print_table() {
+        let table = Table::new(["name", "count", "note"])
+            .with_row(["apples", "12", "fresh"])
+            .with_row(["kiwi", "3", ""])
+            .with_align(2, Align::Center);
+        assert_eq!(
+            table.pretty_print(),
+            "name   | count | note\n-------+-------+------\napples |    12 | fresh\nkiwi   |     3 |"
+        );
+
+        let table = Table::from_rows(["x", "y", "label"], &[(1, 2.5, "a"), (10, -1.0, "日本")]);
+        assert_eq!(
+            table.to_string(),
+            " x |   y | label\n---+-----+------\n 1 | 2.5 | a\n10 |  -1 | 日本"
+        );
+    }
+
+    #[test]
+    fn should_align_wide_characters() {
+        let vec = vec!["日本".to_string(), "ab".to_string()];
+        assert_eq!(vec.pretty_print(), r#"[   "ab", "日本" ]"#);
+    }
 }
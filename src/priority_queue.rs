@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A binary min-heap of keys that can be found again by key, so a key's priority can be changed
+/// or the key removed in O(log n).
+pub struct PriorityQueue<K, P> {
+    heap: Vec<(K, P)>,
+    positions: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> Default for PriorityQueue<K, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> PriorityQueue<K, P> {
+    pub fn new() -> Self {
+        Self {
+            heap: Vec::new(),
+            positions: HashMap::new(),
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+    pub fn contains(&self, key: &K) -> bool {
+        self.positions.contains_key(key)
+    }
+    pub fn priority(&self, key: &K) -> Option<&P> {
+        self.positions.get(key).map(|&i| &self.heap[i].1)
+    }
+    /// Adds `key`, or changes its priority if it's already queued and returns the old one.
+    pub fn push(&mut self, key: K, priority: P) -> Option<P> {
+        if self.contains(&key) {
+            return self.change_priority(&key, priority);
+        }
+        self.positions.insert(key.clone(), self.heap.len());
+        self.heap.push((key, priority));
+        self.sift_up(self.heap.len() - 1);
+        None
+    }
+    pub fn peek_min(&self) -> Option<(&K, &P)> {
+        self.heap.first().map(|(key, priority)| (key, priority))
+    }
+    pub fn pop_min(&mut self) -> Option<(K, P)> {
+        self.remove_at(0)
+    }
+    /// Returns the old priority, or `None` without changes if `key` isn't queued.
+    pub fn change_priority(&mut self, key: &K, priority: P) -> Option<P> {
+        let i = *self.positions.get(key)?;
+        let old = std::mem::replace(&mut self.heap[i].1, priority);
+        if self.heap[i].1 < old {
+            self.sift_up(i);
+        } else {
+            self.sift_down(i);
+        }
+        Some(old)
+    }
+    pub fn remove(&mut self, key: &K) -> Option<P> {
+        let i = *self.positions.get(key)?;
+        self.remove_at(i).map(|(_, priority)| priority)
+    }
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.positions.clear();
+    }
+    fn remove_at(&mut self, i: usize) -> Option<(K, P)> {
+        if i >= self.heap.len() {
+            return None;
+        }
+        let last = self.heap.len() - 1;
+        self.swap(i, last);
+        let (key, priority) = self.heap.pop()?;
+        self.positions.remove(&key);
+        if i < self.heap.len() {
+            self.sift_down(i);
+            self.sift_up(i);
+        }
+        Some((key, priority))
+    }
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if self.heap[i].1 >= self.heap[parent].1 {
+                break;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let smallest = [2 * i + 1, 2 * i + 2]
+                .into_iter()
+                .filter(|&child| child < self.heap.len())
+                .fold(i, |smallest, child| {
+                    if self.heap[child].1 < self.heap[smallest].1 {
+                        child
+                    } else {
+                        smallest
+                    }
+                });
+            if smallest == i {
+                break;
+            }
+            self.swap(i, smallest);
+            i = smallest;
+        }
+    }
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        for i in [a, b] {
+            *self.positions.get_mut(&self.heap[i].0).unwrap() = i;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_pop_in_priority_order() {
+        let mut queue = PriorityQueue::new();
+        for (key, priority) in [("a", 5), ("b", 3), ("c", 8), ("d", 1), ("e", 4)] {
+            queue.push(key, priority);
+        }
+        assert_eq!(queue.change_priority(&"c", 0), Some(8));
+        assert_eq!(queue.push("d", 9), Some(1));
+        assert_eq!(queue.remove(&"b"), Some(3));
+        assert_eq!(queue.change_priority(&"z", 0), None);
+        assert_eq!(queue.peek_min(), Some((&"c", &0)));
+
+        let mut order = vec![];
+        while let Some((key, _)) = queue.pop_min() {
+            order.push(key);
+        }
+        assert_eq!(order, ["c", "e", "a", "d"]);
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where the states of an iterated function start repeating.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// Steps before the first state that's part of the cycle.
+    pub start: u64,
+    pub length: u64,
+}
+
+impl Cycle {
+    /// The smallest step count that reaches the same state as `n` steps.
+    pub fn equivalent_step(&self, n: u64) -> u64 {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Finds the cycle that `step` falls into from `initial` with Brent's algorithm. Only returns if
+/// there is one, e.g. when `step` is deterministic over finitely many states.
+pub fn find_cycle<T: PartialEq + Clone>(initial: T, step: impl Fn(&T) -> T) -> Cycle {
+    let (mut power, mut length) = (1u64, 1u64);
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+/// The state after `n` steps from `initial`, skipping whole cycles.
+pub fn state_after_n_steps<T: PartialEq + Clone>(initial: T, step: impl Fn(&T) -> T, n: u64) -> T {
+    let steps = find_cycle(initial.clone(), &step).equivalent_step(n);
+    (0..steps).fold(initial, |state, _| step(&state))
+}
+
+/// A longest common subsequence of two sequences.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommonSubsequence {
+    pub length: usize,
+    /// `(index in a, index in b)` of each matched item, increasing in both.
+    pub matches: Vec<(usize, usize)>,
+}
+
+/// A longest common contiguous run of two sequences.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CommonSubstring {
+    pub length: usize,
+    pub start_a: usize,
+    pub start_b: usize,
+}
+
+/// The positions of each item in `b`, ascending.
+fn positions<T: Eq + Hash>(b: &[T]) -> HashMap<&T, Vec<usize>> {
+    let mut positions: HashMap<&T, Vec<usize>> = HashMap::new();
+    for (j, item) in b.iter().enumerate() {
+        positions.entry(item).or_default().push(j);
+    }
+    positions
+}
+
+/// Longest common subsequence with Hunt-Szymanski, which only visits matching pairs, so it's fast
+/// on diff-like inputs where most items appear a few times.
+pub fn lcs<T: Eq + Hash>(a: &[T], b: &[T]) -> CommonSubsequence {
+    let positions = positions(b);
+    // tails[k] is the smallest index in b ending a common subsequence of length k + 1
+    let mut tails: Vec<usize> = vec![];
+    let mut tail_nodes: Vec<usize> = vec![];
+    // (i, j, previous node) to walk back the witness
+    let mut nodes: Vec<(usize, usize, Option<usize>)> = vec![];
+    for (i, item) in a.iter().enumerate() {
+        let Some(js) = positions.get(item) else {
+            continue;
+        };
+        // descending, so one item of a can't extend its own match
+        for &j in js.iter().rev() {
+            let k = tails.partition_point(|&tail| tail < j);
+            if k < tails.len() && tails[k] == j {
+                continue;
+            }
+            let previous = k.checked_sub(1).map(|k| tail_nodes[k]);
+            nodes.push((i, j, previous));
+            if k == tails.len() {
+                tails.push(j);
+                tail_nodes.push(nodes.len() - 1);
+            } else {
+                tails[k] = j;
+                tail_nodes[k] = nodes.len() - 1;
+            }
+        }
+    }
+    let mut matches = vec![];
+    let mut node = tail_nodes.last().copied();
+    while let Some(index) = node {
+        let (i, j, previous) = nodes[index];
+        matches.push((i, j));
+        node = previous;
+    }
+    matches.reverse();
+    CommonSubsequence {
+        length: matches.len(),
+        matches,
+    }
+}
+
+/// Longest common contiguous run, the one ending first in `a` on ties. Only matching pairs are
+/// visited and memory is linear in `b`.
+pub fn longest_common_substring<T: Eq + Hash>(a: &[T], b: &[T]) -> CommonSubstring {
+    let positions = positions(b);
+    let mut best = CommonSubstring {
+        length: 0,
+        start_a: 0,
+        start_b: 0,
+    };
+    // previous[j + 1] is the length of the common run ending at a[i - 1] and b[j]
+    let (mut previous, mut current) = (vec![0; b.len() + 1], vec![0; b.len() + 1]);
+    let (mut previous_touched, mut current_touched) = (vec![], vec![]);
+    for (i, item) in a.iter().enumerate() {
+        for &j in positions.get(item).into_iter().flatten() {
+            let length = previous[j] + 1;
+            current[j + 1] = length;
+            current_touched.push(j + 1);
+            if length > best.length {
+                best = CommonSubstring {
+                    length,
+                    start_a: i + 1 - length,
+                    start_b: j + 1 - length,
+                };
+            }
+        }
+        for &j in &previous_touched {
+            previous[j] = 0;
+        }
+        std::mem::swap(&mut previous, &mut current);
+        std::mem::swap(&mut previous_touched, &mut current_touched);
+        current_touched.clear();
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_find_cycle() {
+        let step = |x: &u64| (x * 2 + 1) % 100;
+        let cycle = find_cycle(3, step);
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 0,
+                length: 20
+            }
+        );
+
+        let step = |x: &u64| if *x < 5 { x + 1 } else { 3 };
+        assert_eq!(
+            find_cycle(0, step),
+            Cycle {
+                start: 3,
+                length: 3
+            }
+        );
+        assert_eq!(state_after_n_steps(0, step, 2), 2);
+        assert_eq!(state_after_n_steps(0, step, 1_000_000_000_000), 4);
+    }
+
+    #[test]
+    fn should_find_longest_common_subsequence() {
+        let a: Vec<char> = "ABCBDAB".chars().collect();
+        let b: Vec<char> = "BDCABA".chars().collect();
+        let result = lcs(&a, &b);
+        assert_eq!(result.length, 4);
+        assert!(result
+            .matches
+            .windows(2)
+            .all(|pair| pair[0].0 < pair[1].0 && pair[0].1 < pair[1].1));
+        assert!(result.matches.iter().all(|&(i, j)| a[i] == b[j]));
+
+        let lines = ["fn main() {", "    let x = 1;", "    println!(x);", "}"];
+        let edited = ["fn main() {", "    let x = 2;", "    println!(x);", "}"];
+        assert_eq!(lcs(&lines, &edited).matches, [(0, 0), (2, 2), (3, 3)]);
+        assert_eq!(lcs::<u8>(&[], &[1, 2]).length, 0);
+    }
+
+    #[test]
+    fn should_find_longest_common_substring() {
+        let result = longest_common_substring(b"xabcdey", b"zzbcdabc");
+        assert_eq!(
+            result,
+            CommonSubstring {
+                length: 3,
+                start_a: 1,
+                start_b: 5
+            }
+        );
+        assert_eq!(longest_common_substring(b"abc", b"xyz").length, 0);
+        assert_eq!(longest_common_substring(&[1, 1, 1], &[1, 1]).length, 2);
+    }
+}
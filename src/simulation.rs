@@ -0,0 +1,211 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::time::Duration;
+
+use log::debug;
+
+use crate::common::{AsF64, Numeric};
+#[cfg(feature = "parallel")]
+use crate::parallel::CancellationToken;
+use crate::timer::Timer;
+
+/// Longest uninterrupted sleep while pacing, so cancellation is noticed promptly.
+const PACING_SLICE: Duration = Duration::from_millis(10);
+
+struct Entry<T, N> {
+    time: N,
+    /// Keeps events scheduled for the same time in the order they were scheduled.
+    sequence: u64,
+    event: T,
+}
+
+impl<T, N: Numeric> PartialEq for Entry<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T, N: Numeric> Eq for Entry<T, N> {}
+
+impl<T, N: Numeric> PartialOrd for Entry<T, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T, N: Numeric> Ord for Entry<T, N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed, so BinaryHeap pops the earliest first
+        (other.time, other.sequence).cmp(&(self.time, self.sequence))
+    }
+}
+
+/// Events ordered by simulated time, the skeleton of a discrete-event simulation. Events at the
+/// same time come out in the order they were scheduled.
+pub struct EventQueue<T, N = u64> {
+    events: BinaryHeap<Entry<T, N>>,
+    now: N,
+    next_sequence: u64,
+}
+
+impl<T, N: Numeric> Default for EventQueue<T, N> {
+    fn default() -> Self {
+        Self {
+            events: BinaryHeap::new(),
+            now: N::default(),
+            next_sequence: 0,
+        }
+    }
+}
+
+impl<T, N: Numeric> EventQueue<T, N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// The time of the last event popped, or the last `advance_until` target.
+    pub fn now(&self) -> N {
+        self.now
+    }
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+    pub fn peek_time(&self) -> Option<N> {
+        self.events.peek().map(|entry| entry.time)
+    }
+    /// Panics if `time` is before `now()`.
+    pub fn schedule(&mut self, time: N, event: T) {
+        assert!(
+            time >= self.now,
+            "can't schedule an event at {} before now ({})",
+            time,
+            self.now
+        );
+        self.events.push(Entry {
+            time,
+            sequence: self.next_sequence,
+            event,
+        });
+        self.next_sequence += 1;
+    }
+    pub fn schedule_in(&mut self, delay: N, event: T) {
+        self.schedule(self.now + delay, event);
+    }
+    pub fn pop_next(&mut self) -> Option<(N, T)> {
+        let entry = self.events.pop()?;
+        self.now = entry.time;
+        Some((entry.time, entry.event))
+    }
+    /// Like `pop_next`, but only if the next event is at or before `until`. Handy in a loop that
+    /// schedules more events as it goes.
+    pub fn pop_next_until(&mut self, until: N) -> Option<(N, T)> {
+        if self.peek_time()? > until {
+            return None;
+        }
+        self.pop_next()
+    }
+    /// Pops every event at or before `until` in order and moves `now()` to `until`.
+    pub fn advance_until(&mut self, until: N) -> Vec<(N, T)> {
+        let mut result = vec![];
+        while let Some(next) = self.pop_next_until(until) {
+            result.push(next);
+        }
+        self.now = self.now.max(until);
+        result
+    }
+}
+
+impl<T, N: Numeric + AsF64> EventQueue<T, N> {
+    /// Handles every event, including ones scheduled by `handle`, sleeping so that each event
+    /// happens `unit` of wall-clock time per unit of simulated time after the current `now()`.
+    pub fn run_paced(&mut self, unit: Duration, handle: impl FnMut(&mut Self, N, T)) {
+        self.run_paced_while(unit, || true, handle);
+    }
+    /// Like `run_paced`, stopping early once `token` is cancelled.
+    #[cfg(feature = "parallel")]
+    pub fn run_paced_until_cancelled(
+        &mut self,
+        unit: Duration,
+        token: &CancellationToken,
+        handle: impl FnMut(&mut Self, N, T),
+    ) {
+        self.run_paced_while(unit, || !token.is_cancelled(), handle);
+    }
+    fn run_paced_while(
+        &mut self,
+        unit: Duration,
+        keep_going: impl Fn() -> bool,
+        mut handle: impl FnMut(&mut Self, N, T),
+    ) {
+        let start = self.now;
+        let timer = Timer::start(|elapsed| debug!("Simulation ran for {:?}", elapsed));
+        while let Some(time) = self.peek_time() {
+            let due = unit.mul_f64((time - start).as_f64());
+            loop {
+                if !keep_going() {
+                    return;
+                }
+                let elapsed = timer.elapsed();
+                if elapsed >= due {
+                    break;
+                }
+                std::thread::sleep((due - elapsed).min(PACING_SLICE));
+            }
+            let (time, event) = self.pop_next().expect("peeked above");
+            handle(self, time, event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    #[test]
+    fn should_pop_in_time_order() {
+        let mut queue = EventQueue::new();
+        queue.schedule(5, "c");
+        queue.schedule(1, "a");
+        queue.schedule(5, "d");
+        queue.schedule(3, "b");
+        assert_eq!(queue.len(), 4);
+        assert_eq!(queue.peek_time(), Some(1));
+        assert_eq!(queue.pop_next(), Some((1, "a")));
+        assert_eq!(queue.now(), 1);
+
+        queue.schedule_in(1, "a2");
+        assert_eq!(queue.advance_until(4), [(2, "a2"), (3, "b")]);
+        assert_eq!(queue.now(), 4);
+        assert_eq!(queue.advance_until(10), [(5, "c"), (5, "d")]);
+        assert!(queue.is_empty());
+        assert_eq!(queue.pop_next(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "before now")]
+    fn should_reject_events_in_the_past() {
+        let mut queue = EventQueue::new();
+        queue.advance_until(10);
+        queue.schedule(3, ());
+    }
+
+    #[test]
+    fn should_pace_to_wall_clock() {
+        let mut queue: EventQueue<u32> = EventQueue::new();
+        queue.schedule(0, 3);
+        let start = Instant::now();
+        let mut seen = vec![];
+        queue.run_paced(Duration::from_millis(5), |queue, time, remaining| {
+            seen.push(time);
+            if remaining > 0 {
+                queue.schedule_in(2, remaining - 1);
+            }
+        });
+        assert_eq!(seen, [0, 2, 4, 6]);
+        assert!(start.elapsed() >= Duration::from_millis(30));
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Write};
+use std::hash::Hash;
+
+use thiserror::Error;
+
+type Guard<S, E> = Box<dyn Fn(&S, &E) -> bool>;
+type Hook<S, E> = Box<dyn FnMut(&S, &E)>;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("No transition from {state:?} on {event:?}")]
+pub struct NoTransition<S, E> {
+    pub state: S,
+    /// The event that was fired, handed back.
+    pub event: E,
+}
+
+struct Transition<S, E> {
+    from: S,
+    event: E,
+    to: S,
+    guard: Option<Guard<S, E>>,
+}
+
+/// A finite state machine with transitions registered as `(state, event) -> state`. When several
+/// transitions share a state and event, the first one whose guard passes is taken.
+pub struct Fsm<S, E> {
+    initial: S,
+    state: S,
+    /// In registration order, which is also the order of `to_dot`.
+    transitions: Vec<Transition<S, E>>,
+    by_key: HashMap<(S, E), Vec<usize>>,
+    on_enter: HashMap<S, Vec<Hook<S, E>>>,
+    on_exit: HashMap<S, Vec<Hook<S, E>>>,
+}
+
+impl<S: Hash + Eq + Clone + Debug, E: Hash + Eq + Clone + Debug> Fsm<S, E> {
+    pub fn new(initial: S) -> Self {
+        Self {
+            initial: initial.clone(),
+            state: initial,
+            transitions: vec![],
+            by_key: HashMap::new(),
+            on_enter: HashMap::new(),
+            on_exit: HashMap::new(),
+        }
+    }
+    pub fn with_transition(self, from: S, event: E, to: S) -> Self {
+        self.add_transition(from, event, to, None)
+    }
+    /// Only taken when `guard(state, event)` holds.
+    pub fn with_guarded_transition(
+        self,
+        from: S,
+        event: E,
+        to: S,
+        guard: impl Fn(&S, &E) -> bool + 'static,
+    ) -> Self {
+        self.add_transition(from, event, to, Some(Box::new(guard)))
+    }
+    /// Called with the previous state and the event whenever `state` is entered.
+    pub fn with_on_enter(mut self, state: S, hook: impl FnMut(&S, &E) + 'static) -> Self {
+        self.on_enter.entry(state).or_default().push(Box::new(hook));
+        self
+    }
+    /// Called with the next state and the event whenever `state` is left.
+    pub fn with_on_exit(mut self, state: S, hook: impl FnMut(&S, &E) + 'static) -> Self {
+        self.on_exit.entry(state).or_default().push(Box::new(hook));
+        self
+    }
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+    pub fn can_fire(&self, event: &E) -> bool {
+        self.find(event).is_some()
+    }
+    /// Moves to the next state, running the exit hooks of the current state and then the entry
+    /// hooks of the next one, even when they're the same state.
+    pub fn fire(&mut self, event: E) -> Result<&S, NoTransition<S, E>> {
+        let Some(i) = self.find(&event) else {
+            return Err(NoTransition {
+                state: self.state.clone(),
+                event,
+            });
+        };
+        let to = self.transitions[i].to.clone();
+        for hook in self.on_exit.get_mut(&self.state).into_iter().flatten() {
+            hook(&to, &event);
+        }
+        let from = std::mem::replace(&mut self.state, to);
+        for hook in self.on_enter.get_mut(&self.state).into_iter().flatten() {
+            hook(&from, &event);
+        }
+        Ok(&self.state)
+    }
+    /// The transition graph in Graphviz DOT format. Guarded edges are dashed.
+    pub fn to_dot(&self) -> String {
+        let quote = |value: &dyn Debug| format!("{:?}", format!("{:?}", value));
+        let mut result = String::from("digraph {\n    __start [shape=point];\n");
+        writeln!(result, "    __start -> {};", quote(&self.initial)).unwrap();
+        for transition in &self.transitions {
+            let style = if transition.guard.is_some() {
+                ", style=dashed"
+            } else {
+                ""
+            };
+            writeln!(
+                result,
+                "    {} -> {} [label={}{}];",
+                quote(&transition.from),
+                quote(&transition.to),
+                quote(&transition.event),
+                style
+            )
+            .unwrap();
+        }
+        result.push('}');
+        result
+    }
+    fn add_transition(mut self, from: S, event: E, to: S, guard: Option<Guard<S, E>>) -> Self {
+        self.by_key
+            .entry((from.clone(), event.clone()))
+            .or_default()
+            .push(self.transitions.len());
+        self.transitions.push(Transition {
+            from,
+            event,
+            to,
+            guard,
+        });
+        self
+    }
+    fn find(&self, event: &E) -> Option<usize> {
+        self.by_key
+            .get(&(self.state.clone(), event.clone()))?
+            .iter()
+            .copied()
+            .find(|&i| {
+                self.transitions[i]
+                    .guard
+                    .as_ref()
+                    .is_none_or(|guard| guard(&self.state, event))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    enum Door {
+        Open,
+        Closed,
+        Locked,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    enum Action {
+        Close,
+        Open,
+        Lock,
+    }
+
+    fn door(has_key: Rc<Cell<bool>>) -> Fsm<Door, Action> {
+        Fsm::new(Door::Open)
+            .with_transition(Door::Open, Action::Close, Door::Closed)
+            .with_transition(Door::Closed, Action::Open, Door::Open)
+            .with_guarded_transition(Door::Closed, Action::Lock, Door::Locked, move |_, _| {
+                has_key.get()
+            })
+    }
+
+    #[test]
+    fn should_follow_transitions() {
+        let has_key = Rc::new(Cell::new(false));
+        let mut fsm = door(has_key.clone());
+        assert_eq!(fsm.fire(Action::Close), Ok(&Door::Closed));
+        assert!(!fsm.can_fire(&Action::Lock));
+        assert_eq!(
+            fsm.fire(Action::Lock),
+            Err(NoTransition {
+                state: Door::Closed,
+                event: Action::Lock
+            })
+        );
+
+        has_key.set(true);
+        assert_eq!(fsm.fire(Action::Lock), Ok(&Door::Locked));
+        assert_eq!(fsm.state(), &Door::Locked);
+        assert!(fsm.fire(Action::Open).is_err());
+    }
+
+    #[test]
+    fn should_run_hooks_in_order() {
+        let log = Rc::new(RefCell::new(vec![]));
+        let (enter, exit) = (log.clone(), log.clone());
+        let mut fsm = door(Rc::new(Cell::new(true)))
+            .with_on_exit(Door::Open, move |to, event| {
+                exit.borrow_mut()
+                    .push(format!("exit Open to {:?} on {:?}", to, event))
+            })
+            .with_on_enter(Door::Closed, move |from, _| {
+                enter
+                    .borrow_mut()
+                    .push(format!("enter Closed from {:?}", from))
+            });
+        fsm.fire(Action::Close).unwrap();
+        assert_eq!(
+            *log.borrow(),
+            ["exit Open to Closed on Close", "enter Closed from Open"]
+        );
+    }
+
+    #[test]
+    fn should_export_dot() {
+        let fsm = door(Rc::new(Cell::new(true)));
+        assert_eq!(
+            fsm.to_dot(),
+            "digraph {\n    __start [shape=point];\n    __start -> \"Open\";\n    \
+             \"Open\" -> \"Closed\" [label=\"Close\"];\n    \
+             \"Closed\" -> \"Open\" [label=\"Open\"];\n    \
+             \"Closed\" -> \"Locked\" [label=\"Lock\", style=dashed];\n}"
+        );
+    }
+}
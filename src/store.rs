@@ -0,0 +1,84 @@
+use std::env;
+use std::io;
+use std::path::PathBuf;
+
+/// `file_name` in the per-user data directory for `app_name`, which is created if needed:
+/// `$XDG_DATA_HOME` or `~/.local/share` on Linux, `~/Library/Application Support` on macOS and
+/// `%APPDATA%` on Windows.
+pub fn default_path(app_name: &str, file_name: &str) -> io::Result<PathBuf> {
+    let data_dir = data_dir(|name| env::var_os(name).map(PathBuf::from)).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::NotFound, "no data directory for this user")
+    })?;
+    path_in(data_dir, app_name, file_name)
+}
+
+fn path_in(data_dir: PathBuf, app_name: &str, file_name: &str) -> io::Result<PathBuf> {
+    let dir = data_dir.join(app_name);
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir.join(file_name))
+}
+
+/// Looks variables up through `var`, so tests don't have to change the environment.
+#[cfg(windows)]
+fn data_dir(var: impl Fn(&str) -> Option<PathBuf>) -> Option<PathBuf> {
+    var("APPDATA").filter(|dir| dir.is_absolute())
+}
+
+#[cfg(target_os = "macos")]
+fn data_dir(var: impl Fn(&str) -> Option<PathBuf>) -> Option<PathBuf> {
+    var("HOME")
+        .filter(|home| home.is_absolute())
+        .map(|home| home.join("Library/Application Support"))
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn data_dir(var: impl Fn(&str) -> Option<PathBuf>) -> Option<PathBuf> {
+    // relative values are invalid per the XDG spec and ignored
+    var("XDG_DATA_HOME")
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| {
+            var("HOME")
+                .filter(|home| home.is_absolute())
+                .map(|home| home.join(".local/share"))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(any(windows, target_os = "macos")))]
+    #[test]
+    fn should_prefer_xdg_data_home() {
+        let vars = |xdg: Option<&'static str>| {
+            move |name: &str| match name {
+                "XDG_DATA_HOME" => xdg.map(PathBuf::from),
+                "HOME" => Some(PathBuf::from("/home/me")),
+                _ => None,
+            }
+        };
+        assert_eq!(data_dir(vars(Some("/data"))), Some(PathBuf::from("/data")));
+        assert_eq!(
+            data_dir(vars(Some("relative"))),
+            Some(PathBuf::from("/home/me/.local/share"))
+        );
+        assert_eq!(
+            data_dir(vars(None)),
+            Some(PathBuf::from("/home/me/.local/share"))
+        );
+        assert_eq!(data_dir(|_| None), None);
+    }
+
+    #[test]
+    fn should_create_the_app_directory() {
+        let data_dir = env::temp_dir().join(format!("utils-{}", uuid::Uuid::new_v4()));
+        let path = path_in(data_dir.clone(), "app", "store.json").unwrap();
+        assert_eq!(path, data_dir.join("app").join("store.json"));
+        assert!(data_dir.join("app").is_dir());
+        assert_eq!(
+            path_in(data_dir.clone(), "app", "store.json").unwrap(),
+            path
+        );
+        std::fs::remove_dir_all(data_dir).unwrap();
+    }
+}
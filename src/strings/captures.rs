@@ -0,0 +1,113 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use regex::{Captures, Regex};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CaptureError {
+    #[error("Invalid pattern: {0}")]
+    Pattern(#[from] regex::Error),
+    #[error("{text:?} doesn't match {pattern}")]
+    NoMatch { pattern: String, text: String },
+    #[error("No group named {0:?} matched")]
+    MissingGroup(String),
+    #[error("Can't parse group {name:?} from {value:?}: {message}")]
+    Parse {
+        name: String,
+        value: String,
+        message: String,
+    },
+}
+
+/// Built from the named groups of a regex match. With the `derive` feature,
+/// `#[derive(FromCaptures)]` parses each field from the group of the same name.
+pub trait FromCaptures: Sized {
+    fn from_captures(captures: &Captured) -> Result<Self, CaptureError>;
+}
+
+pub struct Captured<'a> {
+    captures: Captures<'a>,
+}
+
+impl Captured<'_> {
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<T, CaptureError>
+    where
+        T::Err: Display,
+    {
+        let value = self
+            .captures
+            .name(name)
+            .ok_or_else(|| CaptureError::MissingGroup(name.to_string()))?
+            .as_str();
+        value.parse().map_err(|e: T::Err| CaptureError::Parse {
+            name: name.to_string(),
+            value: value.to_string(),
+            message: e.to_string(),
+        })
+    }
+}
+
+/// Compiles `pattern` on every call; use `parse_captured_with` in a loop.
+pub fn parse_captured<T: FromCaptures>(pattern: &str, text: &str) -> Result<T, CaptureError> {
+    parse_captured_with(&Regex::new(pattern)?, text)
+}
+
+pub fn parse_captured_with<T: FromCaptures>(regex: &Regex, text: &str) -> Result<T, CaptureError> {
+    let captures = regex.captures(text).ok_or_else(|| CaptureError::NoMatch {
+        pattern: regex.as_str().to_string(),
+        text: text.to_string(),
+    })?;
+    T::from_captures(&Captured { captures })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Claim {
+        id: u32,
+        x: u32,
+        y: u32,
+    }
+
+    impl FromCaptures for Claim {
+        fn from_captures(captures: &Captured) -> Result<Self, CaptureError> {
+            Ok(Self {
+                id: captures.get("id")?,
+                x: captures.get("x")?,
+                y: captures.get("y")?,
+            })
+        }
+    }
+
+    const PATTERN: &str = r"#(?P<id>\d+) @ (?P<x>\d+),(?P<y>-?\d+)";
+
+    #[test]
+    fn should_parse_captures() {
+        let claim: Claim = parse_captured(PATTERN, "#123 @ 3,2: 5x4").unwrap();
+        assert_eq!(
+            claim,
+            Claim {
+                id: 123,
+                x: 3,
+                y: 2
+            }
+        );
+
+        let error = parse_captured::<Claim>(PATTERN, "#1 @ 3,-2").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"Can't parse group "y" from "-2": invalid digit found in string"#
+        );
+        assert!(matches!(
+            parse_captured::<Claim>(PATTERN, "nope"),
+            Err(CaptureError::NoMatch { .. })
+        ));
+        assert!(matches!(
+            parse_captured::<Claim>(r"#(?P<id>\d+)", "#1"),
+            Err(CaptureError::MissingGroup(name)) if name == "x"
+        ));
+    }
+}
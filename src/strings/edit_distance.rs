@@ -0,0 +1,165 @@
+/// One step of turning one string into another, in order from the start of both.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditOp {
+    Keep(char),
+    Substitute { from: char, to: char },
+    Insert(char),
+    Delete(char),
+}
+
+impl EditOp {
+    pub fn cost(&self) -> usize {
+        match self {
+            EditOp::Keep(_) => 0,
+            _ => 1,
+        }
+    }
+}
+
+/// The fewest single-character insertions, deletions and substitutions turning `a` into `b`.
+/// Keeps one row of the table, over the shorter string.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    *last_row(&long, &short).last().unwrap()
+}
+
+/// The number of positions where `a` and `b` differ, or `None` if they have different lengths.
+pub fn hamming(a: &str, b: &str) -> Option<usize> {
+    let (mut a, mut b) = (a.chars(), b.chars());
+    let mut distance = 0;
+    loop {
+        match (a.next(), b.next()) {
+            (Some(x), Some(y)) => distance += usize::from(x != y),
+            (None, None) => return Some(distance),
+            _ => return None,
+        }
+    }
+}
+
+/// A cheapest list of operations turning `a` into `b`; their total cost is
+/// `levenshtein(a, b)`. Uses Hirschberg's algorithm, so memory stays linear.
+pub fn edit_operations(a: &str, b: &str) -> Vec<EditOp> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut ops = Vec::with_capacity(a.len().max(b.len()));
+    align(&a, &b, &mut ops);
+    ops
+}
+
+fn align(a: &[char], b: &[char], ops: &mut Vec<EditOp>) {
+    match a {
+        [] => ops.extend(b.iter().map(|&ch| EditOp::Insert(ch))),
+        _ if b.is_empty() => ops.extend(a.iter().map(|&ch| EditOp::Delete(ch))),
+        [ch] => {
+            let (op, rest) = match b.iter().position(|other| other == ch) {
+                Some(j) => {
+                    ops.extend(b[..j].iter().map(|&ch| EditOp::Insert(ch)));
+                    (EditOp::Keep(*ch), &b[j + 1..])
+                }
+                None => (
+                    EditOp::Substitute {
+                        from: *ch,
+                        to: b[0],
+                    },
+                    &b[1..],
+                ),
+            };
+            ops.push(op);
+            ops.extend(rest.iter().map(|&ch| EditOp::Insert(ch)));
+        }
+        _ => {
+            let middle = a.len() / 2;
+            let left = last_row(&a[..middle], b);
+            let reversed_a: Vec<char> = a[middle..].iter().rev().copied().collect();
+            let reversed_b: Vec<char> = b.iter().rev().copied().collect();
+            let right = last_row(&reversed_a, &reversed_b);
+            let split = (0..=b.len())
+                .min_by_key(|&k| left[k] + right[b.len() - k])
+                .unwrap();
+            align(&a[..middle], &b[..split], ops);
+            align(&a[middle..], &b[split..], ops);
+        }
+    }
+}
+
+/// The distances from all of `a` to each prefix of `b`.
+fn last_row<T: PartialEq>(a: &[T], b: &[T]) -> Vec<usize> {
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, x) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, y) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(x != y);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(ops: &[EditOp]) -> (String, String) {
+        let (mut from, mut to) = (String::new(), String::new());
+        for op in ops {
+            match *op {
+                EditOp::Keep(ch) => {
+                    from.push(ch);
+                    to.push(ch);
+                }
+                EditOp::Substitute { from: x, to: y } => {
+                    from.push(x);
+                    to.push(y);
+                }
+                EditOp::Insert(ch) => to.push(ch),
+                EditOp::Delete(ch) => from.push(ch),
+            }
+        }
+        (from, to)
+    }
+
+    #[test]
+    fn should_measure_distances() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("sitting", "kitten"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("flaw", "lawn"), 2);
+        assert_eq!(levenshtein("über", "uber"), 1);
+        assert_eq!(hamming("karolin", "kathrin"), Some(3));
+        assert_eq!(hamming("abc", "ab"), None);
+    }
+
+    #[test]
+    fn should_list_cheapest_operations() {
+        assert_eq!(
+            edit_operations("cat", "cut"),
+            [
+                EditOp::Keep('c'),
+                EditOp::Substitute { from: 'a', to: 'u' },
+                EditOp::Keep('t')
+            ]
+        );
+        for (a, b) in [
+            ("kitten", "sitting"),
+            ("intention", "execution"),
+            ("", "abc"),
+            ("abc", ""),
+            ("a", "xyz"),
+            ("algorithm", "altruistic"),
+        ] {
+            let ops = edit_operations(a, b);
+            assert_eq!(apply(&ops), (a.to_string(), b.to_string()));
+            assert_eq!(
+                ops.iter().map(EditOp::cost).sum::<usize>(),
+                levenshtein(a, b),
+                "{} -> {}",
+                a,
+                b
+            );
+        }
+    }
+}
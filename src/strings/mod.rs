@@ -0,0 +1,118 @@
+use std::str::FromStr;
+
+#[cfg(feature = "regex")]
+mod captures;
+mod edit_distance;
+mod rolling_hash;
+
+#[cfg(feature = "regex")]
+pub use captures::{parse_captured, parse_captured_with, CaptureError, Captured, FromCaptures};
+pub use edit_distance::{edit_operations, hamming, levenshtein, EditOp};
+pub use rolling_hash::RollingHash;
+#[cfg(all(feature = "regex", feature = "derive"))]
+pub use utils_derive::FromCaptures;
+
+/// Every integer in `text`, with a `-` right before the digits making it negative. Numbers that
+/// don't fit `T` are skipped; for unsigned `T` the sign is dropped.
+pub fn extract_ints<T: FromStr>(text: &str) -> Vec<T> {
+    let bytes = text.as_bytes();
+    let mut result = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        let signed = if start > 0 && bytes[start - 1] == b'-' {
+            start - 1
+        } else {
+            start
+        };
+        if let Some(value) = text[signed..i]
+            .parse()
+            .ok()
+            .or_else(|| text[start..i].parse().ok())
+        {
+            result.push(value);
+        }
+    }
+    result
+}
+
+/// Splits on `separator`, trims each item and parses the non-empty ones.
+pub fn split_parse<T: FromStr>(text: &str, separator: &str) -> Result<Vec<T>, T::Err> {
+    text.split(separator)
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+/// One row of characters per line.
+pub fn chars_grid(text: &str) -> Vec<Vec<char>> {
+    text.lines().map(|line| line.chars().collect()).collect()
+}
+
+/// Byte offsets of the non-overlapping, case-insensitive matches of `needle` in `haystack`.
+pub fn find_all_ignore_case(haystack: &str, needle: &str) -> Vec<usize> {
+    let needle: Vec<char> = needle.chars().flat_map(char::to_lowercase).collect();
+    if needle.is_empty() {
+        return vec![];
+    }
+    let mut result = vec![];
+    let mut next = 0;
+    for (i, _) in haystack.char_indices() {
+        if i < next {
+            continue;
+        }
+        if let Some(len) = match_len(&haystack[i..], &needle) {
+            result.push(i);
+            next = i + len;
+        }
+    }
+    result
+}
+
+/// How many bytes of `text` match the lowercased `needle` from its start.
+fn match_len(text: &str, needle: &[char]) -> Option<usize> {
+    let mut matched = 0;
+    for (i, ch) in text.char_indices() {
+        for lower in ch.to_lowercase() {
+            if needle.get(matched) != Some(&lower) {
+                return None;
+            }
+            matched += 1;
+        }
+        if matched == needle.len() {
+            return Some(i + ch.len_utf8());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_extract_ints() {
+        let line = "Sensor at x=-12, y=7: beacon is at x=3-4, y=18446744073709551616";
+        assert_eq!(extract_ints::<i64>(line), [-12, 7, 3, -4]);
+        assert_eq!(extract_ints::<u32>(line), [12, 7, 3, 4]);
+        assert_eq!(extract_ints::<i32>("no numbers"), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn should_parse_and_search() {
+        assert_eq!(split_parse::<u8>("1, 2,,3 ", ","), Ok(vec![1, 2, 3]));
+        assert!(split_parse::<u8>("1,x", ",").is_err());
+        assert_eq!(chars_grid("ab\r\ncd"), [['a', 'b'], ['c', 'd']]);
+        assert_eq!(find_all_ignore_case("Abc aBC abab", "ab"), [0, 4, 8, 10]);
+        assert_eq!(find_all_ignore_case("aaa", "AA"), [0]);
+        assert_eq!(find_all_ignore_case("Ünïcode ÜNÏ", "ünï"), [0, 10]);
+    }
+}
@@ -0,0 +1,102 @@
+use std::ops::Range;
+
+const MODULI: [u64; 2] = [1_000_000_007, 998_244_353];
+const BASES: [u64; 2] = [911_382_323, 972_663_749];
+
+/// Polynomial hashes of every prefix of a text under two moduli, so that any substring's hash
+/// is an O(1) lookup. Works on bytes; ranges are byte offsets.
+#[derive(Clone, Debug)]
+pub struct RollingHash {
+    /// `prefixes[k][i]` hashes the first `i` bytes under `MODULI[k]`.
+    prefixes: [Vec<u64>; 2],
+    powers: [Vec<u64>; 2],
+}
+
+impl RollingHash {
+    pub fn new(text: &str) -> Self {
+        Self::from_bytes(text.as_bytes())
+    }
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let build = |k: usize| {
+            let (modulus, base) = (MODULI[k], BASES[k]);
+            let mut prefix = Vec::with_capacity(bytes.len() + 1);
+            let mut power = Vec::with_capacity(bytes.len() + 1);
+            prefix.push(0);
+            power.push(1);
+            for (i, &byte) in bytes.iter().enumerate() {
+                prefix.push((prefix[i] * base + byte as u64 + 1) % modulus);
+                power.push(power[i] * base % modulus);
+            }
+            (prefix, power)
+        };
+        let (first, first_powers) = build(0);
+        let (second, second_powers) = build(1);
+        Self {
+            prefixes: [first, second],
+            powers: [first_powers, second_powers],
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.prefixes[0].len() - 1
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// The hash of the bytes at `range`. Equal substrings always hash the same, whichever text
+    /// they come from. Panics if `range` is out of bounds.
+    pub fn hash(&self, range: Range<usize>) -> u64 {
+        assert!(
+            range.start <= range.end && range.end <= self.len(),
+            "range {:?} out of bounds for length {}",
+            range,
+            self.len()
+        );
+        let part = |k: usize| {
+            let prefix = &self.prefixes[k];
+            let shifted = prefix[range.start] * self.powers[k][range.len()] % MODULI[k];
+            (prefix[range.end] + MODULI[k] - shifted) % MODULI[k]
+        };
+        (part(0) << 32) | part(1)
+    }
+    /// Byte offsets of every occurrence of `pattern`, overlapping ones included. Matches are
+    /// decided by hash alone, so a false positive is possible but vanishingly unlikely.
+    pub fn find_all_occurrences(&self, pattern: &str) -> Vec<usize> {
+        let len = pattern.len();
+        if len == 0 || len > self.len() {
+            return vec![];
+        }
+        let target = Self::new(pattern).hash(0..len);
+        (0..=self.len() - len)
+            .filter(|&start| self.hash(start..start + len) == target)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_hash_equal_substrings_equally() {
+        let hash = RollingHash::new("abracadabra");
+        assert_eq!(hash.len(), 11);
+        assert_eq!(hash.hash(0..4), hash.hash(7..11));
+        assert_ne!(hash.hash(0..4), hash.hash(1..5));
+        assert_eq!(hash.hash(3..3), hash.hash(5..5));
+        assert_eq!(hash.hash(0..4), RollingHash::new("abra").hash(0..4));
+        assert_ne!(hash.hash(0..1), RollingHash::new("\0a").hash(0..2));
+    }
+
+    #[test]
+    fn should_find_all_occurrences() {
+        let hash = RollingHash::new("abracadabra");
+        assert_eq!(hash.find_all_occurrences("abra"), [0, 7]);
+        assert_eq!(hash.find_all_occurrences("a"), [0, 3, 5, 7, 10]);
+        assert!(hash.find_all_occurrences("abrx").is_empty());
+        assert!(hash.find_all_occurrences("").is_empty());
+        assert_eq!(
+            RollingHash::new("aaaa").find_all_occurrences("aa"),
+            [0, 1, 2]
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use uuid::Uuid;
+
+use crate::temp::{
+    cleanup_strategy, registry, root, CleanupStrategy, TempDir, TempFile, EXEC_NAME,
+};
+
+/// What follows the prefix in a temp path's name.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TempNaming {
+    #[default]
+    Uuid,
+    /// A process-wide counter, zero-padded: `myexe_0001.json`, `myexe_0002.json`.
+    Counter,
+    /// Milliseconds since the Unix epoch.
+    Timestamp,
+}
+
+static COUNTER: AtomicU64 = AtomicU64::new(1);
+/// Non-UUID names can collide with leftovers from earlier runs; give up after this many.
+const MAX_ATTEMPTS: u32 = 1000;
+
+/// Where and how a `TempFile` or `TempDir` is created. By default it goes in `temp::root()` and
+/// is named `<exec name>_<uuid>`.
+#[derive(Clone, Debug, Default)]
+pub struct TempFileBuilder {
+    dir: Option<PathBuf>,
+    prefix: Option<String>,
+    suffix: Option<String>,
+    cleanup: Option<CleanupStrategy>,
+    naming: TempNaming,
+    #[cfg(unix)]
+    mode: Option<u32>,
+}
+
+impl TempFileBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Creating the file next to its final destination keeps a later rename atomic.
+    pub fn with_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefix = Some(prefix.to_string());
+        self
+    }
+    pub fn with_suffix(mut self, suffix: &str) -> Self {
+        self.suffix = Some(suffix.to_string());
+        self
+    }
+    /// Overrides `temp::cleanup_strategy()` for this path.
+    pub fn with_cleanup(mut self, cleanup: CleanupStrategy) -> Self {
+        self.cleanup = Some(cleanup);
+        self
+    }
+    /// Sortable, readable names for debugging sessions, instead of a UUID.
+    pub fn with_naming(mut self, naming: TempNaming) -> Self {
+        self.naming = naming;
+        self
+    }
+    #[cfg(unix)]
+    pub fn with_mode(mut self, mode: u32) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+    pub fn create_file(&self) -> std::io::Result<TempFile> {
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        if let Some(mode) = self.mode {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(mode);
+        }
+        let path = self.create(|path| options.open(path).map(|_| ()))?;
+        log::trace!("Created temp file: {:?}", path);
+        registry::track(&path);
+        Ok(TempFile {
+            path,
+            cleanup: self.cleanup.unwrap_or_else(cleanup_strategy),
+        })
+    }
+    pub fn create_dir(&self) -> std::io::Result<TempDir> {
+        let mut builder = std::fs::DirBuilder::new();
+        #[cfg(unix)]
+        if let Some(mode) = self.mode {
+            use std::os::unix::fs::DirBuilderExt;
+            builder.mode(mode);
+        }
+        let path = self.create(|path| builder.create(path))?;
+        log::trace!("Created temp dir: {:?}", path);
+        registry::track(&path);
+        Ok(TempDir {
+            path,
+            cleanup: self.cleanup.unwrap_or_else(cleanup_strategy),
+        })
+    }
+    fn create(&self, create: impl Fn(&Path) -> std::io::Result<()>) -> std::io::Result<PathBuf> {
+        let mut attempt = 0;
+        loop {
+            let path = self.path(attempt);
+            match create(&path) {
+                Err(e)
+                    if e.kind() == std::io::ErrorKind::AlreadyExists
+                        && self.naming != TempNaming::Uuid
+                        && attempt + 1 < MAX_ATTEMPTS =>
+                {
+                    attempt += 1
+                }
+                result => return result.map(|_| path),
+            }
+        }
+    }
+    fn path(&self, attempt: u32) -> PathBuf {
+        let id = match self.naming {
+            TempNaming::Uuid => Uuid::new_v4().to_string(),
+            TempNaming::Counter => format!("{:04}", COUNTER.fetch_add(1, Ordering::Relaxed)),
+            TempNaming::Timestamp => {
+                let millis = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |elapsed| elapsed.as_millis());
+                match attempt {
+                    0 => millis.to_string(),
+                    attempt => format!("{}-{}", millis, attempt),
+                }
+            }
+        };
+        let name = match &self.prefix {
+            Some(prefix) => format!("{}{}", prefix, id),
+            None => format!("{}_{}", *EXEC_NAME, id),
+        };
+        let mut path = self.dir.clone().unwrap_or_else(root);
+        path.push(format!("{}{}", name, self.suffix.as_deref().unwrap_or("")));
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temp::FileDetails;
+
+    #[test]
+    fn should_create_in_custom_location() {
+        let parent = TempDir::new().unwrap();
+        let builder = TempFileBuilder::new()
+            .with_dir(parent.get_path())
+            .with_prefix("report-")
+            .with_suffix(".json");
+        let file = builder.create_file().unwrap();
+        assert_eq!(file.get_path().parent(), Some(parent.get_path()));
+        let name = file.get_name().unwrap();
+        assert!(name.starts_with("report-") && name.ends_with(".json"));
+
+        let dir = builder.create_dir().unwrap();
+        assert!(dir.get_path().is_dir());
+        assert_eq!(dir.get_path().parent(), Some(parent.get_path()));
+    }
+
+    #[test]
+    fn should_name_sequentially() {
+        let parent = TempDir::new().unwrap();
+        let builder = TempFileBuilder::new()
+            .with_dir(parent.get_path())
+            .with_prefix("run_")
+            .with_suffix(".json")
+            .with_naming(TempNaming::Counter);
+        let first = builder.create_file().unwrap();
+        let second = builder.create_file().unwrap();
+        let number = |file: &TempFile| -> u64 {
+            let name = file.get_name().unwrap();
+            assert_eq!(name.len(), "run_0000.json".len(), "{}", name);
+            name["run_".len().."run_0000".len()].parse().unwrap()
+        };
+        assert!(number(&first) < number(&second));
+
+        let builder = builder.with_naming(TempNaming::Timestamp);
+        let first = builder.create_file().unwrap();
+        let second = builder.create_file().unwrap();
+        assert_ne!(first.get_path(), second.get_path());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn should_set_mode() {
+        use std::os::unix::fs::PermissionsExt;
+        let file = TempFileBuilder::new()
+            .with_mode(0o600)
+            .create_file()
+            .unwrap();
+        let mode = std::fs::metadata(file.get_path())
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
@@ -0,0 +1,334 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::RwLock;
+
+use backon::BlockingRetryable;
+use backon::ExponentialBuilder;
+use lazy_static::lazy_static;
+
+mod builder;
+mod registry;
+mod snapshot;
+mod spooled;
+
+pub use builder::{TempFileBuilder, TempNaming};
+pub use registry::{cleanup_on_exit, cleanup_on_exit_and_panic};
+pub use snapshot::{Snapshot, SnapshotDiff, SnapshotEntry};
+pub use spooled::SpooledTempFile;
+
+fn get_exec_name() -> Option<String> {
+    Some(env::current_exe().ok()?.file_name()?.to_str()?.to_string())
+}
+
+lazy_static! {
+    static ref EXEC_NAME: String = get_exec_name().unwrap_or("rust".to_string());
+    static ref ENV_ROOT: Option<PathBuf> = env::var_os(ROOT_ENV_VAR).map(PathBuf::from);
+    static ref ROOT: RwLock<Option<PathBuf>> = RwLock::new(None);
+}
+
+/// Read once, the first time a temp path is needed.
+pub const ROOT_ENV_VAR: &str = "UTILS_TEMP_ROOT";
+
+/// Creates temp files and directories under `path` from now on, unless a builder sets its own
+/// directory.
+pub fn set_root(path: impl AsRef<Path>) {
+    *ROOT.write().unwrap() = Some(path.as_ref().to_path_buf());
+}
+
+/// The directory set with `set_root`, else the `UTILS_TEMP_ROOT` environment variable, else
+/// the system temp directory.
+pub fn root() -> PathBuf {
+    resolve_root(ROOT.read().unwrap().clone(), ENV_ROOT.clone())
+}
+
+fn resolve_root(explicit: Option<PathBuf>, from_env: Option<PathBuf>) -> PathBuf {
+    explicit
+        .or(from_env.filter(|path| !path.as_os_str().is_empty()))
+        .unwrap_or_else(env::temp_dir)
+}
+
+/// How a temp path is removed when it's dropped.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CleanupStrategy {
+    /// Retry with exponential backoff, blocking the dropping thread.
+    Inline,
+    /// Try once, then keep retrying on a new background thread (inline without `parallel`).
+    #[default]
+    Background,
+    /// Try once and log a failure.
+    FireAndForget,
+}
+
+static CLEANUP: AtomicU8 = AtomicU8::new(CleanupStrategy::Background as u8);
+
+/// The strategy for temp paths created from now on, unless a builder sets its own.
+pub fn set_cleanup_strategy(strategy: CleanupStrategy) {
+    CLEANUP.store(strategy as u8, Ordering::Relaxed);
+}
+
+pub fn cleanup_strategy() -> CleanupStrategy {
+    match CLEANUP.load(Ordering::Relaxed) {
+        0 => CleanupStrategy::Inline,
+        1 => CleanupStrategy::Background,
+        _ => CleanupStrategy::FireAndForget,
+    }
+}
+
+pub struct TempFile {
+    path: std::path::PathBuf,
+    cleanup: CleanupStrategy,
+}
+
+pub struct TempDir {
+    path: std::path::PathBuf,
+    cleanup: CleanupStrategy,
+}
+
+impl TempFile {
+    pub fn new(extension: Option<&str>) -> std::io::Result<Self> {
+        Self::builder()
+            .with_suffix(extension.unwrap_or(""))
+            .create_file()
+    }
+    pub fn builder() -> TempFileBuilder {
+        TempFileBuilder::new()
+    }
+    /// Disarms the cleanup and returns the path, leaving the file in place.
+    pub fn keep(self) -> std::path::PathBuf {
+        let mut this = std::mem::ManuallyDrop::new(self);
+        log::trace!("Keeping temp file: {:?}", this.path);
+        registry::untrack(&this.path);
+        std::mem::take(&mut this.path)
+    }
+    /// Syncs the file to disk and moves it to `destination`, replacing any file already there.
+    /// Readers of `destination` see either the old contents or the complete new ones.
+    pub fn promote_to(self, destination: impl AsRef<Path>) -> std::io::Result<()> {
+        let destination = destination.as_ref();
+        std::fs::File::open(&self.path)?.sync_all()?;
+        match std::fs::rename(&self.path, destination) {
+            Ok(()) => {
+                self.keep();
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+                // copy next to the destination so the final rename stays on one filesystem
+                let dir = destination
+                    .parent()
+                    .filter(|dir| !dir.as_os_str().is_empty())
+                    .unwrap_or(Path::new("."));
+                let staged = Self::builder().with_dir(dir).create_file()?;
+                std::fs::copy(&self.path, &staged.path)?;
+                std::fs::File::open(&staged.path)?.sync_all()?;
+                std::fs::rename(&staged.path, destination)?;
+                staged.keep();
+            }
+            Err(e) => return Err(e),
+        }
+        log::trace!("Promoted temp file to: {:?}", destination);
+        Ok(())
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        log::trace!("Removing temp file: {:?}", self.path);
+        registry::untrack(&self.path);
+        remove(std::mem::take(&mut self.path), self.cleanup);
+    }
+}
+
+impl TempDir {
+    pub fn new() -> std::io::Result<Self> {
+        Self::builder().create_dir()
+    }
+    pub fn builder() -> TempFileBuilder {
+        TempFileBuilder::new()
+    }
+    /// Records every file below the directory with its size and content hash.
+    pub fn snapshot(&self) -> std::io::Result<Snapshot> {
+        Snapshot::of(&self.path)
+    }
+    /// What changed in the directory since `snapshot` was taken.
+    pub fn diff(&self, snapshot: &Snapshot) -> std::io::Result<SnapshotDiff> {
+        Ok(snapshot.diff(&self.snapshot()?))
+    }
+    /// Disarms the cleanup and returns the path, leaving the directory and its contents in place.
+    pub fn keep(self) -> std::path::PathBuf {
+        let mut this = std::mem::ManuallyDrop::new(self);
+        log::trace!("Keeping temp dir: {:?}", this.path);
+        registry::untrack(&this.path);
+        std::mem::take(&mut this.path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        log::trace!("Removing temp dir: {:?}", self.path);
+        registry::untrack(&self.path);
+        remove(std::mem::take(&mut self.path), self.cleanup);
+    }
+}
+
+fn remove(path: std::path::PathBuf, strategy: CleanupStrategy) {
+    let is_dir = path.is_dir();
+    let remove = move || {
+        let result = if is_dir {
+            std::fs::remove_dir_all(&path)
+        } else {
+            std::fs::remove_file(&path)
+        };
+        // e.g. already removed by `cleanup_on_exit`
+        match result {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    };
+    let log_error = move |e: std::io::Error| {
+        log::error!(
+            "Failed to remove temp {}: {:?}",
+            if is_dir { "dir" } else { "file" },
+            e
+        )
+    };
+    match strategy {
+        CleanupStrategy::Inline => {
+            if let Err(e) = remove.retry(&ExponentialBuilder::default()).call() {
+                log_error(e);
+            }
+        }
+        // without threads, retry inline instead
+        #[cfg(not(feature = "parallel"))]
+        CleanupStrategy::Background => {
+            if let Err(e) = remove.retry(&ExponentialBuilder::default()).call() {
+                log_error(e);
+            }
+        }
+        #[cfg(feature = "parallel")]
+        CleanupStrategy::Background => {
+            if remove().is_err() {
+                std::thread::spawn(move || {
+                    if let Err(e) = remove.retry(&ExponentialBuilder::default()).call() {
+                        log_error(e);
+                    }
+                });
+            }
+        }
+        CleanupStrategy::FireAndForget => {
+            if let Err(e) = remove() {
+                log_error(e);
+            }
+        }
+    }
+}
+
+pub trait FileDetails {
+    fn get_path(&self) -> &std::path::Path;
+    fn get_name(&self) -> Option<&str> {
+        self.get_path().file_name()?.to_str()
+    }
+}
+
+impl FileDetails for TempFile {
+    fn get_path(&self) -> &std::path::Path {
+        &self.path
+    }
+}
+
+impl FileDetails for TempDir {
+    fn get_path(&self) -> &std::path::Path {
+        &self.path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_delete_temp_file() {
+        let temp_file = TempFile::new(Some(".txt")).unwrap();
+        let path = temp_file.path.clone();
+        assert!(path.exists());
+        drop(temp_file);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn should_delete_temp_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path.clone();
+        assert!(path.exists());
+
+        let mut file_path = path.clone();
+        file_path.push("test.txt");
+        std::fs::File::create(&file_path).unwrap();
+        assert!(file_path.exists());
+
+        drop(temp_dir);
+        assert!(!path.exists());
+        assert!(!file_path.exists());
+    }
+
+    #[test]
+    fn should_resolve_root() {
+        let explicit = PathBuf::from("/scratch/a");
+        let from_env = PathBuf::from("/scratch/b");
+        assert_eq!(
+            resolve_root(Some(explicit.clone()), Some(from_env.clone())),
+            explicit
+        );
+        assert_eq!(resolve_root(None, Some(from_env.clone())), from_env);
+        assert_eq!(resolve_root(None, Some(PathBuf::new())), env::temp_dir());
+        assert_eq!(resolve_root(None, None), env::temp_dir());
+
+        // other tests run concurrently, so only ever set the default
+        set_root(env::temp_dir());
+        assert_eq!(root(), env::temp_dir());
+    }
+
+    #[test]
+    fn should_clean_up_with_each_strategy() {
+        for strategy in [
+            CleanupStrategy::Inline,
+            CleanupStrategy::Background,
+            CleanupStrategy::FireAndForget,
+        ] {
+            let file = TempFile::builder()
+                .with_cleanup(strategy)
+                .create_file()
+                .unwrap();
+            let path = file.path.clone();
+            drop(file);
+            assert!(!path.exists());
+        }
+        assert_eq!(cleanup_strategy(), CleanupStrategy::Background);
+    }
+
+    #[test]
+    fn should_keep_temp_file_and_dir() {
+        let path = TempFile::new(None).unwrap().keep();
+        assert!(path.exists());
+        std::fs::remove_file(&path).unwrap();
+
+        let path = TempDir::new().unwrap().keep();
+        assert!(path.is_dir());
+        std::fs::remove_dir(&path).unwrap();
+    }
+
+    #[test]
+    fn should_promote_temp_file() {
+        let dir = TempDir::new().unwrap();
+        let destination = dir.path.join("out.txt");
+        std::fs::write(&destination, "old").unwrap();
+
+        let file = TempFile::new(None).unwrap();
+        let path = file.path.clone();
+        std::fs::write(&path, "new").unwrap();
+        file.promote_to(&destination).unwrap();
+        assert!(!path.exists());
+        assert_eq!(std::fs::read_to_string(&destination).unwrap(), "new");
+
+        let missing = TempFile::new(None).unwrap();
+        assert!(missing.promote_to(dir.path.join("no/such/dir")).is_err());
+    }
+}
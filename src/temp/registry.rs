@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, Once};
+
+use lazy_static::lazy_static;
+
+/// Paths of live temp files and directories.
+#[derive(Default)]
+pub(crate) struct Registry {
+    paths: Mutex<HashSet<PathBuf>>,
+}
+
+impl Registry {
+    pub(crate) fn track(&self, path: &Path) {
+        self.paths.lock().unwrap().insert(path.to_path_buf());
+    }
+    pub(crate) fn untrack(&self, path: &Path) {
+        self.paths.lock().unwrap().remove(path);
+    }
+    /// Removes every tracked path right away, without retrying.
+    pub(crate) fn remove_all(&self) {
+        // a panic while the lock was held must not stop the cleanup
+        let paths: Vec<PathBuf> = match self.paths.lock() {
+            Ok(mut paths) => paths.drain().collect(),
+            Err(poisoned) => poisoned.into_inner().drain().collect(),
+        };
+        for path in paths {
+            let result = if path.is_dir() {
+                std::fs::remove_dir_all(&path)
+            } else {
+                std::fs::remove_file(&path)
+            };
+            if let Err(e) = result {
+                log::error!("Failed to remove temp path {:?}: {:?}", path, e);
+            }
+        }
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+    static ref REGISTRY: Registry = Registry::default();
+}
+
+pub(crate) fn track(path: &Path) {
+    if ENABLED.load(Ordering::Relaxed) {
+        REGISTRY.track(path);
+    }
+}
+
+pub(crate) fn untrack(path: &Path) {
+    if ENABLED.load(Ordering::Relaxed) {
+        REGISTRY.untrack(path);
+    }
+}
+
+/// Removes the temp files and directories created from now on when the process is interrupted
+/// (SIGINT, SIGTERM or SIGHUP on Unix), since their `Drop` won't run then. Temp paths created
+/// before the first call aren't tracked.
+pub fn cleanup_on_exit() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        ENABLED.store(true, Ordering::Relaxed);
+        #[cfg(unix)]
+        if let Err(e) = install_signal_handler() {
+            log::error!("Failed to install temp cleanup signal handler: {:?}", e);
+        }
+    });
+}
+
+/// Like `cleanup_on_exit`, and also removes them when the main thread panics. Only for programs
+/// that never catch a main-thread panic: the paths are removed as soon as the panic starts, even
+/// if it's caught and the program carries on. Runs the previously installed hook afterwards.
+pub fn cleanup_on_exit_and_panic() {
+    cleanup_on_exit();
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if std::thread::current().name() == Some("main") {
+                REGISTRY.remove_all();
+            }
+            previous(info);
+        }));
+    });
+}
+
+#[cfg(unix)]
+fn install_signal_handler() -> std::io::Result<()> {
+    use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM};
+    let mut signals = signal_hook::iterator::Signals::new([SIGINT, SIGTERM, SIGHUP])?;
+    std::thread::Builder::new()
+        .name("temp-cleanup".to_string())
+        .spawn(move || {
+            if let Some(signal) = signals.forever().next() {
+                REGISTRY.remove_all();
+                let _ = signal_hook::low_level::emulate_default_handler(signal);
+            }
+        })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temp::{FileDetails, TempDir, TempFile};
+
+    #[test]
+    fn should_remove_tracked_paths() {
+        let registry = Registry::default();
+        let file = TempFile::new(None).unwrap();
+        let dir = TempDir::new().unwrap();
+        let untracked = TempFile::new(None).unwrap();
+        registry.track(file.get_path());
+        registry.track(dir.get_path());
+        registry.track(untracked.get_path());
+        registry.untrack(untracked.get_path());
+
+        registry.remove_all();
+        assert!(!file.get_path().exists());
+        assert!(!dir.get_path().exists());
+        assert!(untracked.get_path().exists());
+    }
+}
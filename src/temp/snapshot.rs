@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::hash::{DefaultHasher, Hasher};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SnapshotEntry {
+    pub is_dir: bool,
+    pub size: u64,
+    /// Hash of the contents. Only comparable within one process.
+    pub hash: u64,
+}
+
+/// Every file and directory below a root, keyed by path relative to it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    entries: BTreeMap<PathBuf, SnapshotEntry>,
+}
+
+impl Snapshot {
+    pub fn of(root: impl AsRef<Path>) -> std::io::Result<Self> {
+        let mut result = Self::default();
+        result.add_dir(root.as_ref(), Path::new(""))?;
+        Ok(result)
+    }
+    fn add_dir(&mut self, root: &Path, relative: &Path) -> std::io::Result<()> {
+        for entry in std::fs::read_dir(root.join(relative))? {
+            let entry = entry?;
+            let relative = relative.join(entry.file_name());
+            if entry.file_type()?.is_dir() {
+                self.entries.insert(
+                    relative.clone(),
+                    SnapshotEntry {
+                        is_dir: true,
+                        size: 0,
+                        hash: 0,
+                    },
+                );
+                self.add_dir(root, &relative)?;
+            } else {
+                let (size, hash) = hash_file(&entry.path())?;
+                self.entries.insert(
+                    relative,
+                    SnapshotEntry {
+                        is_dir: false,
+                        size,
+                        hash,
+                    },
+                );
+            }
+        }
+        Ok(())
+    }
+    pub fn entries(&self) -> &BTreeMap<PathBuf, SnapshotEntry> {
+        &self.entries
+    }
+    /// What changed going from `self` to `newer`.
+    pub fn diff(&self, newer: &Snapshot) -> SnapshotDiff {
+        let mut diff = SnapshotDiff::default();
+        for (path, entry) in &newer.entries {
+            match self.entries.get(path) {
+                None => diff.created.push(path.clone()),
+                Some(old) if old != entry => diff.modified.push(path.clone()),
+                Some(_) => {}
+            }
+        }
+        diff.deleted = self
+            .entries
+            .keys()
+            .filter(|path| !newer.entries.contains_key(*path))
+            .cloned()
+            .collect();
+        diff
+    }
+}
+
+fn hash_file(path: &Path) -> std::io::Result<(u64, u64)> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = DefaultHasher::new();
+    let mut buffer = [0u8; 8192];
+    let mut size = 0;
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.write(&buffer[..read]);
+        size += read as u64;
+    }
+    Ok((size, hasher.finish()))
+}
+
+/// Paths are relative to the snapshot root and sorted.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SnapshotDiff {
+    pub created: Vec<PathBuf>,
+    pub modified: Vec<PathBuf>,
+    pub deleted: Vec<PathBuf>,
+}
+
+impl SnapshotDiff {
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty() && self.modified.is_empty() && self.deleted.is_empty()
+    }
+}
+
+/// One line per change: `+ created`, `~ modified` or `- deleted`.
+impl Display for SnapshotDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = [
+            ('+', &self.created),
+            ('~', &self.modified),
+            ('-', &self.deleted),
+        ]
+        .into_iter()
+        .flat_map(|(mark, paths)| {
+            paths
+                .iter()
+                .map(move |path| format!("{} {}", mark, path.display()))
+        })
+        .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temp::{FileDetails, TempDir};
+
+    #[test]
+    fn should_diff_snapshots() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.get_path();
+        std::fs::write(root.join("same.txt"), "same").unwrap();
+        std::fs::write(root.join("changed.txt"), "before").unwrap();
+        std::fs::write(root.join("gone.txt"), "gone").unwrap();
+        let before = dir.snapshot().unwrap();
+        assert_eq!(before.entries().len(), 3);
+
+        std::fs::write(root.join("changed.txt"), "after!").unwrap();
+        std::fs::remove_file(root.join("gone.txt")).unwrap();
+        std::fs::create_dir(root.join("sub")).unwrap();
+        std::fs::write(root.join("sub").join("new.txt"), "new").unwrap();
+
+        let diff = dir.diff(&before).unwrap();
+        assert_eq!(
+            diff.created,
+            vec![PathBuf::from("sub"), Path::new("sub").join("new.txt")]
+        );
+        assert_eq!(diff.modified, vec![PathBuf::from("changed.txt")]);
+        assert_eq!(diff.deleted, vec![PathBuf::from("gone.txt")]);
+        assert_eq!(
+            diff.to_string(),
+            format!(
+                "+ sub\n+ {}\n~ changed.txt\n- gone.txt",
+                Path::new("sub").join("new.txt").display()
+            )
+        );
+        assert!(dir.diff(&dir.snapshot().unwrap()).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,136 @@
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use crate::temp::{FileDetails, TempFile, TempFileBuilder};
+
+enum Spool {
+    Memory(Cursor<Vec<u8>>),
+    File(TempFile, std::fs::File),
+}
+
+/// Kept in memory until it grows past `max_size` bytes or its path is requested, then moved
+/// to a `TempFile`.
+pub struct SpooledTempFile {
+    max_size: usize,
+    builder: TempFileBuilder,
+    spool: Spool,
+}
+
+impl SpooledTempFile {
+    pub fn new(max_size: usize) -> Self {
+        Self::with_builder(max_size, TempFileBuilder::new())
+    }
+    /// `builder` decides where the file goes once it is rolled over.
+    pub fn with_builder(max_size: usize, builder: TempFileBuilder) -> Self {
+        Self {
+            max_size,
+            builder,
+            spool: Spool::Memory(Cursor::new(vec![])),
+        }
+    }
+    pub fn is_rolled_over(&self) -> bool {
+        matches!(self.spool, Spool::File(..))
+    }
+    /// Moves the contents to disk, keeping the current position.
+    pub fn roll_over(&mut self) -> std::io::Result<()> {
+        if let Spool::Memory(cursor) = &self.spool {
+            let temp_file = self.builder.create_file()?;
+            let mut file = std::fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open(temp_file.get_path())?;
+            file.write_all(cursor.get_ref())?;
+            file.seek(SeekFrom::Start(cursor.position()))?;
+            self.spool = Spool::File(temp_file, file);
+        }
+        Ok(())
+    }
+    pub fn path(&mut self) -> std::io::Result<&Path> {
+        self.roll_over()?;
+        match &self.spool {
+            Spool::File(temp_file, _) => Ok(temp_file.get_path()),
+            Spool::Memory(_) => unreachable!("just rolled over"),
+        }
+    }
+}
+
+impl Read for SpooledTempFile {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match &mut self.spool {
+            Spool::Memory(cursor) => cursor.read(buf),
+            Spool::File(_, file) => file.read(buf),
+        }
+    }
+}
+
+impl Write for SpooledTempFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if let Spool::Memory(cursor) = &self.spool {
+            if cursor.position() as usize + buf.len() > self.max_size {
+                self.roll_over()?;
+            }
+        }
+        match &mut self.spool {
+            Spool::Memory(cursor) => cursor.write(buf),
+            Spool::File(_, file) => file.write(buf),
+        }
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        match &mut self.spool {
+            Spool::Memory(_) => Ok(()),
+            Spool::File(_, file) => file.flush(),
+        }
+    }
+}
+
+impl Seek for SpooledTempFile {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        match &mut self.spool {
+            Spool::Memory(cursor) => cursor.seek(pos),
+            Spool::File(_, file) => file.seek(pos),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_stay_in_memory_below_threshold() {
+        let mut file = SpooledTempFile::new(16);
+        file.write_all(b"hello").unwrap();
+        assert!(!file.is_rolled_over());
+        file.seek(SeekFrom::Start(1)).unwrap();
+        let mut text = String::new();
+        file.read_to_string(&mut text).unwrap();
+        assert_eq!(text, "ello");
+    }
+
+    #[test]
+    fn should_roll_over_past_threshold() {
+        let mut file = SpooledTempFile::new(8);
+        file.write_all(b"hello").unwrap();
+        file.write_all(b" world").unwrap();
+        assert!(file.is_rolled_over());
+        file.seek(SeekFrom::Start(0)).unwrap();
+        let mut text = String::new();
+        file.read_to_string(&mut text).unwrap();
+        assert_eq!(text, "hello world");
+
+        let path = file.path().unwrap().to_path_buf();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "hello world");
+        drop(file);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn should_roll_over_when_path_is_requested() {
+        let mut file = SpooledTempFile::new(1024);
+        file.write_all(b"abc").unwrap();
+        let path = file.path().unwrap().to_path_buf();
+        assert_eq!(std::fs::read(&path).unwrap(), b"abc");
+        file.write_all(b"d").unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"abcd");
+    }
+}
@@ -15,6 +15,9 @@ impl Timeout {
     pub fn is_done(&self) -> bool {
         self.start.elapsed() >= self.duration
     }
+    pub fn remaining(&self) -> Duration {
+        self.duration.saturating_sub(self.start.elapsed())
+    }
     pub fn restart(&mut self) {
         self.start = Instant::now();
     }
@@ -42,5 +45,6 @@ mod tests {
         assert!(!timeout.is_done());
         std::thread::sleep(Duration::from_millis(60));
         assert!(timeout.is_done());
+        assert_eq!(timeout.remaining(), Duration::ZERO);
     }
 }
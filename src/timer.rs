@@ -1,34 +1,314 @@
 use std::time::{Duration, Instant};
 
+use crate::pretty_print::{make_bins, render_histogram};
+use crate::timeout::Timeout;
+
 pub struct Timer<T>
 where
-    T: FnMut(Duration) -> (),
+    T: FnMut(Duration),
 {
     start: Instant,
-    on_finish: T,
+    on_finish: Option<T>,
 }
 
 impl<T> Timer<T>
 where
-    T: FnMut(Duration) -> (),
+    T: FnMut(Duration),
 {
     pub fn start(on_finish: T) -> Self
     where
-        T: FnMut(Duration) -> (),
+        T: FnMut(Duration),
     {
         Self {
             start: Instant::now(),
-            on_finish,
+            on_finish: Some(on_finish),
+        }
+    }
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+    pub fn finish(mut self) -> Duration {
+        self.fire()
+    }
+    /// Drops the timer without calling `on_finish`.
+    pub fn cancel(mut self) {
+        self.on_finish = None;
+    }
+    fn fire(&mut self) -> Duration {
+        let elapsed = self.start.elapsed();
+        if let Some(mut on_finish) = self.on_finish.take() {
+            on_finish(elapsed);
         }
+        elapsed
     }
 }
 
 impl<T> Drop for Timer<T>
 where
-    T: FnMut(Duration) -> (),
+    T: FnMut(Duration),
 {
     fn drop(&mut self) {
-        (self.on_finish)(self.start.elapsed());
+        self.fire();
+    }
+}
+
+#[cfg(feature = "derive")]
+pub use utils_derive::timed;
+
+#[doc(hidden)]
+pub use log::Level as __Level;
+
+/// The guard behind `#[timed]`.
+#[doc(hidden)]
+pub fn __timed(
+    target: &'static str,
+    name: &'static str,
+    level: log::Level,
+) -> Timer<impl FnMut(Duration)> {
+    Timer::start(move |elapsed| log::log!(target: target, level, "{} took {:?}", name, elapsed))
+}
+
+/// Tracks progress towards a known item count. The rate is an exponential moving average so
+/// that short bursts don't make the eta jump around.
+pub struct ProgressTracker {
+    label: String,
+    total: usize,
+    done: usize,
+    start: Instant,
+    last_sample: Instant,
+    last_sample_done: usize,
+    smoothed_rate: Option<f64>,
+    timeout: Timeout,
+    suppress_logs: bool,
+}
+
+impl ProgressTracker {
+    const SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+    const SMOOTHING: f64 = 0.3;
+
+    pub fn new(total_items: usize) -> Self {
+        let now = Instant::now();
+        Self {
+            label: "progress".to_string(),
+            total: total_items,
+            done: 0,
+            start: now,
+            last_sample: now,
+            last_sample_done: 0,
+            smoothed_rate: None,
+            timeout: Timeout::start(Duration::from_secs(5)),
+            suppress_logs: false,
+        }
+    }
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = label.to_string();
+        self
+    }
+    pub fn with_log_interval(mut self, log_interval: Duration) -> Self {
+        self.timeout = Timeout::start(log_interval);
+        self
+    }
+    pub fn with_no_logs(mut self) -> Self {
+        self.suppress_logs = true;
+        self
+    }
+    pub fn inc(&mut self, n: usize) {
+        self.inc_at(n, Instant::now());
+    }
+    fn inc_at(&mut self, n: usize, now: Instant) {
+        self.done = (self.done + n).min(self.total);
+        let since_sample = now - self.last_sample;
+        if since_sample >= Self::SAMPLE_INTERVAL {
+            let sample = (self.done - self.last_sample_done) as f64 / since_sample.as_secs_f64();
+            self.smoothed_rate = Some(match self.smoothed_rate {
+                Some(rate) => Self::SMOOTHING * sample + (1.0 - Self::SMOOTHING) * rate,
+                None => sample,
+            });
+            self.last_sample = now;
+            self.last_sample_done = self.done;
+        }
+        if !self.suppress_logs && self.timeout.is_done() {
+            log::info!("{}", self);
+            self.timeout.restart();
+        }
+    }
+    pub fn done(&self) -> usize {
+        self.done
+    }
+    pub fn total(&self) -> usize {
+        self.total
+    }
+    pub fn is_finished(&self) -> bool {
+        self.done >= self.total
+    }
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.done as f64 / self.total as f64
+        }
+    }
+    /// Items per second. Falls back to the overall average until the first sample is taken.
+    pub fn rate(&self) -> f64 {
+        self.smoothed_rate.unwrap_or_else(|| {
+            let elapsed = self.start.elapsed().as_secs_f64();
+            if elapsed > 0.0 {
+                self.done as f64 / elapsed
+            } else {
+                0.0
+            }
+        })
+    }
+    /// Estimated time until all items are done. Returns `Duration::MAX` while no progress has
+    /// been made, since no estimate is possible yet.
+    pub fn eta(&self) -> Duration {
+        let remaining = self.total - self.done;
+        if remaining == 0 {
+            return Duration::ZERO;
+        }
+        let rate = self.rate();
+        if rate <= 0.0 {
+            return Duration::MAX;
+        }
+        Duration::try_from_secs_f64(remaining as f64 / rate).unwrap_or(Duration::MAX)
+    }
+}
+
+impl std::fmt::Display for ProgressTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let eta = self.eta();
+        write!(
+            f,
+            "[{}] {}/{} ({:.1}%) rate={:.1}/s eta=",
+            self.label,
+            self.done,
+            self.total,
+            self.fraction() * 100.0,
+            self.rate()
+        )?;
+        if eta == Duration::MAX {
+            write!(f, "?")
+        } else {
+            write!(f, "{}s", eta.as_secs())
+        }
+    }
+}
+
+/// Collects duration samples and reports percentiles, which expose latency spikes that a
+/// plain average hides.
+#[derive(Clone, Debug, Default)]
+pub struct DurationHistogram {
+    samples: Vec<Duration>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DurationSummary {
+    pub count: usize,
+    pub min: Duration,
+    pub mean: Duration,
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+impl DurationHistogram {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn record(&mut self, duration: Duration) {
+        self.samples.push(duration);
+    }
+    pub fn time<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let result = f();
+        self.record(start.elapsed());
+        result
+    }
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+    pub fn samples(&self) -> &[Duration] {
+        &self.samples
+    }
+    /// Nearest-rank percentile, `p` in `0.0..=100.0`.
+    pub fn percentile(&self, p: f64) -> Option<Duration> {
+        let sorted = self.sorted();
+        Self::percentile_of(&sorted, p)
+    }
+    pub fn summary(&self) -> Option<DurationSummary> {
+        let sorted = self.sorted();
+        Some(DurationSummary {
+            count: sorted.len(),
+            min: *sorted.first()?,
+            mean: Self::mean_of(&sorted),
+            p50: Self::percentile_of(&sorted, 50.0)?,
+            p90: Self::percentile_of(&sorted, 90.0)?,
+            p99: Self::percentile_of(&sorted, 99.0)?,
+            max: *sorted.last()?,
+        })
+    }
+    pub fn histogram(&self, bins: usize) -> String {
+        let values: Vec<f64> = self.samples.iter().map(|d| d.as_secs_f64()).collect();
+        let rows: Vec<(String, usize)> = make_bins(&values, bins)
+            .into_iter()
+            .map(|bin| {
+                (
+                    format!(
+                        "{:?}..{:?}",
+                        Duration::from_secs_f64(bin.start),
+                        Duration::from_secs_f64(bin.end)
+                    ),
+                    bin.count,
+                )
+            })
+            .collect();
+        render_histogram(&rows, 40)
+    }
+    fn sorted(&self) -> Vec<Duration> {
+        let mut sorted = self.samples.clone();
+        sorted.sort();
+        sorted
+    }
+    /// Summed in nanoseconds, so neither the total nor the sample count can overflow.
+    fn mean_of(samples: &[Duration]) -> Duration {
+        let nanos = samples.iter().map(Duration::as_nanos).sum::<u128>() / samples.len() as u128;
+        Duration::new(
+            (nanos / 1_000_000_000) as u64,
+            (nanos % 1_000_000_000) as u32,
+        )
+    }
+    fn percentile_of(sorted: &[Duration], p: f64) -> Option<Duration> {
+        if sorted.is_empty() {
+            return None;
+        }
+        let rank = ((p.clamp(0.0, 100.0) / 100.0) * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.max(1) - 1])
+    }
+}
+
+impl std::fmt::Display for DurationSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "n={} min={:?} mean={:?} p50={:?} p90={:?} p99={:?} max={:?}",
+            self.count, self.min, self.mean, self.p50, self.p90, self.p99, self.max
+        )
+    }
+}
+
+impl std::fmt::Display for DurationHistogram {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.summary() {
+            Some(summary) => write!(f, "{}\n{}", summary, self.histogram(10)),
+            None => write!(f, "n=0"),
+        }
     }
 }
 
@@ -46,4 +326,62 @@ mod tests {
         }
         assert_eq!(value, 1);
     }
+
+    #[test]
+    fn should_finish_once_and_cancel() {
+        let mut calls = 0;
+        let timer = Timer::start(|_| calls += 1);
+        let elapsed = timer.elapsed();
+        assert!(timer.finish() >= elapsed);
+        assert_eq!(calls, 1);
+
+        let timer = Timer::start(|_| calls += 1);
+        timer.cancel();
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn should_estimate_eta() {
+        let mut tracker = ProgressTracker::new(100).with_no_logs();
+        assert_eq!(tracker.eta(), Duration::MAX);
+        let start = tracker.start;
+        tracker.inc_at(30, start + Duration::from_millis(300));
+        assert_eq!(tracker.rate(), 100.0);
+        assert_eq!(tracker.eta(), Duration::from_millis(700));
+        // too soon for a new sample
+        tracker.inc_at(10, start + Duration::from_millis(400));
+        assert_eq!(tracker.rate(), 100.0);
+        tracker.inc_at(10, start + Duration::from_millis(550));
+        assert!((tracker.rate() - 94.0).abs() < 1e-9);
+        tracker.inc(100);
+        assert!(tracker.is_finished());
+        assert_eq!(tracker.done(), 100);
+        assert_eq!(tracker.eta(), Duration::ZERO);
+    }
+
+    #[test]
+    fn should_report_percentiles() {
+        let mut histogram = DurationHistogram::new();
+        assert_eq!(histogram.summary(), None);
+        for ms in 1..=100 {
+            histogram.record(Duration::from_millis(ms));
+        }
+        let summary = histogram.summary().unwrap();
+        assert_eq!(summary.count, 100);
+        assert_eq!(summary.min, Duration::from_millis(1));
+        assert_eq!(summary.p50, Duration::from_millis(50));
+        assert_eq!(summary.p90, Duration::from_millis(90));
+        assert_eq!(summary.p99, Duration::from_millis(99));
+        assert_eq!(summary.max, Duration::from_millis(100));
+        assert_eq!(histogram.percentile(0.0), Some(Duration::from_millis(1)));
+        assert_eq!(summary.mean, Duration::from_micros(50_500));
+        assert_eq!(
+            DurationHistogram::mean_of(&[Duration::MAX, Duration::MAX]),
+            Duration::MAX
+        );
+
+        let text = histogram.histogram(4);
+        assert_eq!(text.lines().count(), 4);
+        assert!(text.lines().all(|line| line.ends_with(" 25")), "{}", text);
+    }
 }
@@ -0,0 +1,266 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Field, Fields, Index, ItemFn, LitStr,
+};
+
+/// Today's UTC date as a literal count of days since 1970-01-01, or the day of
+/// `SOURCE_DATE_EPOCH` when it's set. Used by `utils::compile_warning_until!`.
+#[proc_macro]
+pub fn build_day(_input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let seconds = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs())
+        });
+    let day = proc_macro2::Literal::i64_unsuffixed((seconds / 86_400) as i64);
+    quote!(#day).into()
+}
+
+/// Logs how long each call to the function takes, at debug level unless
+/// `#[timed(log_level = "info")]` says otherwise. Works on async fns too, where the time runs
+/// from the first poll to completion.
+#[proc_macro_attribute]
+pub fn timed(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let mut level = None;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("log_level") {
+            level = Some(meta.value()?.parse::<LitStr>()?);
+            Ok(())
+        } else {
+            Err(meta.error("expected `log_level`"))
+        }
+    });
+    parse_macro_input!(args with parser);
+    let level = match level
+        .as_ref()
+        .map(|level| (level, level.value().to_lowercase()))
+    {
+        None => quote!(Debug),
+        Some((_, value)) if value == "error" => quote!(Error),
+        Some((_, value)) if value == "warn" => quote!(Warn),
+        Some((_, value)) if value == "info" => quote!(Info),
+        Some((_, value)) if value == "debug" => quote!(Debug),
+        Some((_, value)) if value == "trace" => quote!(Trace),
+        Some((level, _)) => {
+            return syn::Error::new_spanned(level, "expected error, warn, info, debug or trace")
+                .to_compile_error()
+                .into();
+        }
+    };
+    let mut item = parse_macro_input!(input as ItemFn);
+    let name = item.sig.ident.to_string();
+    item.block.stmts.insert(
+        0,
+        parse_quote! {
+            let __timed_guard = ::utils::timer::__timed(
+                ::std::module_path!(),
+                #name,
+                ::utils::timer::__Level::#level,
+            );
+        },
+    );
+    quote!(#item).into()
+}
+
+/// Implements `utils::pretty_print::PrettyPrint` by printing each field's own `PrettyPrint` output,
+/// with the same options, in an aligned key/value block. Fields marked `#[pretty_print(debug)]`
+/// print with `Debug` instead, for types that don't implement `PrettyPrint`. Field types that use
+/// a generic parameter are bounded on whichever trait prints them.
+#[proc_macro_derive(PrettyPrint, attributes(pretty_print))]
+pub fn derive_pretty_print(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let mut input = parse_macro_input!(input as DeriveInput);
+    let params: Vec<_> = input
+        .generics
+        .type_params()
+        .map(|p| p.ident.clone())
+        .collect();
+    let all_fields: Vec<Field> = match &input.data {
+        Data::Struct(data) => data.fields.iter().cloned().collect(),
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .flat_map(|variant| &variant.fields)
+            .cloned()
+            .collect(),
+        Data::Union(_) => vec![],
+    };
+    let predicates = &mut input.generics.make_where_clause().predicates;
+    for field in all_fields {
+        let ty = &field.ty;
+        if !mentions_any(ty.to_token_stream(), &params) {
+            continue;
+        }
+        match is_debug(&field) {
+            Ok(true) => predicates.push(parse_quote!(#ty: ::std::fmt::Debug)),
+            Ok(false) => predicates.push(parse_quote!(#ty: ::utils::pretty_print::PrettyPrint)),
+            Err(error) => return error.to_compile_error().into(),
+        }
+    }
+    let name = &input.ident;
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let (bindings, entries) = match fields(&data.fields) {
+                Ok(fields) => fields,
+                Err(error) => return error.to_compile_error().into(),
+            };
+            quote! {
+                let #name #bindings = self;
+                ::utils::pretty_print::pretty_print_fields_with(
+                    stringify!(#name),
+                    ::std::option::Option::None,
+                    &[#(#entries),*],
+                    options,
+                )
+            }
+        }
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let ident = &variant.ident;
+                let (bindings, entries) = fields(&variant.fields)?;
+                Ok(quote! {
+                    #name::#ident #bindings => ::utils::pretty_print::pretty_print_fields_with(
+                        stringify!(#name),
+                        ::std::option::Option::Some(stringify!(#ident)),
+                        &[#(#entries),*],
+                        options,
+                    )
+                })
+            });
+            let arms = match arms.collect::<syn::Result<Vec<_>>>() {
+                Ok(arms) => arms,
+                Err(error) => return error.to_compile_error().into(),
+            };
+            quote! {
+                match self {
+                    #(#arms,)*
+                }
+            }
+        }
+        Data::Union(_) => {
+            return syn::Error::new_spanned(name, "PrettyPrint can't be derived for unions")
+                .to_compile_error()
+                .into();
+        }
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote! {
+        impl #impl_generics ::utils::pretty_print::PrettyPrint for #name #ty_generics #where_clause {
+            fn pretty_print(&self) -> ::std::string::String {
+                self.pretty_print_with(&::utils::pretty_print::Options::default())
+            }
+            fn pretty_print_with(
+                &self,
+                options: &::utils::pretty_print::Options,
+            ) -> ::std::string::String {
+                #body
+            }
+        }
+    }
+    .into()
+}
+
+/// Implements `utils::strings::FromCaptures` by parsing each field from the regex group of the
+/// same name.
+#[proc_macro_derive(FromCaptures)]
+pub fn derive_from_captures(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields.named.iter().map(|f| &f.ident),
+            _ => {
+                return syn::Error::new_spanned(name, "FromCaptures needs named fields")
+                    .to_compile_error()
+                    .into();
+            }
+        },
+        _ => {
+            return syn::Error::new_spanned(name, "FromCaptures can only be derived for structs")
+                .to_compile_error()
+                .into();
+        }
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote! {
+        impl #impl_generics ::utils::strings::FromCaptures for #name #ty_generics #where_clause {
+            fn from_captures(
+                captures: &::utils::strings::Captured,
+            ) -> ::std::result::Result<Self, ::utils::strings::CaptureError> {
+                ::std::result::Result::Ok(Self {
+                    #(#fields: captures.get(stringify!(#fields))?,)*
+                })
+            }
+        }
+    }
+    .into()
+}
+
+/// A pattern binding every field to a generated name, so fields can't shadow the `options`
+/// parameter, and the `(key, value)` entries built from those bindings.
+fn fields(fields: &Fields) -> syn::Result<(TokenStream, Vec<TokenStream>)> {
+    let mut keys = vec![];
+    let mut bindings = vec![];
+    let mut entries = vec![];
+    for (i, field) in fields.iter().enumerate() {
+        let (key, binding) = match &field.ident {
+            Some(ident) => (ident.to_token_stream(), format_ident!("__field_{}", ident)),
+            None => (
+                Index::from(i).to_token_stream(),
+                format_ident!("__field_{}", i),
+            ),
+        };
+        entries.push(entry(&key, &binding, is_debug(field)?));
+        keys.push(key);
+        bindings.push(binding);
+    }
+    Ok(match fields {
+        Fields::Named(_) => (quote!({ #(#keys: #bindings),* }), entries),
+        Fields::Unnamed(_) => (quote!(( #(#bindings),* )), entries),
+        Fields::Unit => (quote!(), entries),
+    })
+}
+
+fn entry(key: impl ToTokens, binding: impl ToTokens, debug: bool) -> TokenStream {
+    if debug {
+        return quote!((stringify!(#key), ::std::format!("{:?}", #binding)));
+    }
+    quote!((
+        stringify!(#key),
+        ::utils::pretty_print::PrettyPrint::pretty_print_with(#binding, options),
+    ))
+}
+
+/// Whether the field is marked `#[pretty_print(debug)]`.
+fn is_debug(field: &Field) -> syn::Result<bool> {
+    let mut debug = false;
+    for attr in &field.attrs {
+        if attr.path().is_ident("pretty_print") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("debug") {
+                    debug = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `debug`"))
+                }
+            })?;
+        }
+    }
+    Ok(debug)
+}
+
+fn mentions_any(tokens: TokenStream, idents: &[Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => idents.contains(&ident),
+        TokenTree::Group(group) => mentions_any(group.stream(), idents),
+        _ => false,
+    })
+}
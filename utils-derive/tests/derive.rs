@@ -0,0 +1,182 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use utils::pretty_print::PrettyPrint;
+
+#[derive(PrettyPrint)]
+struct Config {
+    name: String,
+    retries: usize,
+    tags: Vec<&'static str>,
+}
+
+#[derive(PrettyPrint)]
+struct Pair<T>(T, T);
+
+#[derive(PrettyPrint)]
+struct Workspace<T> {
+    root: PathBuf,
+    timeout: Duration,
+    #[pretty_print(debug)]
+    endpoint: SocketAddr,
+    #[pretty_print(debug)]
+    tag: T,
+}
+
+#[derive(PrettyPrint)]
+struct Limits {
+    options: usize,
+    r#type: &'static str,
+}
+
+#[derive(PrettyPrint)]
+struct Release<T> {
+    owner: Option<&'static str>,
+    builds: BTreeMap<&'static str, Vec<T>>,
+}
+
+#[derive(PrettyPrint)]
+enum Shape {
+    Point,
+    Circle { radius: f64 },
+}
+
+#[test]
+fn should_derive_for_structs() {
+    let config = Config {
+        name: "search".to_string(),
+        retries: 3,
+        tags: vec!["a", "b"],
+    };
+    assert_eq!(
+        config.pretty_print(),
+        "Config {\n  name:    \"search\"\n  retries: 3\n  tags:    [ \"a\", \"b\" ]\n}"
+    );
+    assert_eq!(Pair(1, 2).pretty_print(), "Pair {\n  0: 1\n  1: 2\n}");
+    let limits = Limits {
+        options: 1000,
+        r#type: "soft",
+    };
+    assert_eq!(
+        limits.pretty_print_with(&utils::pretty_print::Options::new().with_digit_separator(',')),
+        "Limits {\n  options: 1,000\n  r#type:  \"soft\"\n}"
+    );
+}
+
+#[test]
+fn should_print_debug_fields() {
+    let workspace = Workspace {
+        root: PathBuf::from("/srv/app"),
+        timeout: Duration::from_secs(5),
+        endpoint: SocketAddr::from(([127, 0, 0, 1], 8080)),
+        tag: Ordering::Less,
+    };
+    assert_eq!(
+        workspace.pretty_print(),
+        "Workspace {\n  root:     \"/srv/app\"\n  timeout:  5s\n  endpoint: 127.0.0.1:8080\n  tag:      Less\n}"
+    );
+}
+
+#[test]
+fn should_derive_json_like() {
+    let config = Config {
+        name: "search".to_string(),
+        retries: 3,
+        tags: vec!["b", "a"],
+    };
+    assert_eq!(
+        config.to_pretty_json_like(),
+        "{\n  \"name\": \"search\",\n  \"retries\": 3,\n  \"tags\": [\n    \"a\",\n    \"b\"\n  ]\n}"
+    );
+    assert_eq!(Shape::Point.to_pretty_json_like(), "\"Point\"");
+
+    let release = Release {
+        owner: None,
+        builds: [("linux", vec![2, 1]), ("mac", vec![])]
+            .into_iter()
+            .collect(),
+    };
+    assert_eq!(
+        release.to_pretty_json_like(),
+        "{\n  \"owner\": null,\n  \"builds\": {\n    \"linux\": [\n      1,\n      2\n    ],\n    \"mac\": []\n  }\n}"
+    );
+    let release = Release {
+        owner: Some("ops"),
+        ..release
+    };
+    assert_eq!(
+        release.pretty_print(),
+        "Release {\n  owner:  Some(\"ops\")\n  builds: { \"linux\": [ 1, 2 ],         \"mac\": [] }\n}"
+    );
+}
+
+#[test]
+fn should_derive_for_enums() {
+    assert_eq!(Shape::Point.pretty_print(), "Shape::Point");
+    assert_eq!(
+        Shape::Circle { radius: 1.5 }.pretty_print(),
+        "Shape::Circle {\n  radius: 1.5\n}"
+    );
+}
+
+#[derive(Debug, PartialEq, utils::strings::FromCaptures)]
+struct Claim {
+    id: u32,
+    x: i64,
+    name: String,
+}
+
+#[test]
+fn should_derive_from_captures() {
+    let claim: Claim = utils::strings::parse_captured(
+        r"#(?P<id>\d+) @ (?P<x>-?\d+) (?P<name>\w+)",
+        "#7 @ -3 left",
+    )
+    .unwrap();
+    assert_eq!(
+        claim,
+        Claim {
+            id: 7,
+            x: -3,
+            name: "left".to_string()
+        }
+    );
+}
+
+#[utils::timer::timed]
+fn collatz_steps(mut n: u64) -> u32 {
+    let mut steps = 0;
+    while n != 1 {
+        if steps > 1000 {
+            return u32::MAX;
+        }
+        n = if n.is_multiple_of(2) {
+            n / 2
+        } else {
+            3 * n + 1
+        };
+        steps += 1;
+    }
+    steps
+}
+
+#[utils::timer::timed(log_level = "info")]
+async fn add_async<T: std::ops::Add<Output = T>>(a: T, b: T) -> T {
+    a + b
+}
+
+#[test]
+fn should_keep_timed_functions_working() {
+    assert_eq!(collatz_steps(27), 111);
+
+    let mut future = std::pin::pin!(add_async(2, 3));
+    let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+    assert_eq!(
+        future.as_mut().poll(&mut context),
+        std::task::Poll::Ready(5)
+    );
+}